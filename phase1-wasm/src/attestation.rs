@@ -0,0 +1,94 @@
+use crate::{
+    errors::Phase1Error,
+    phase1::{validate_parameters, ContributionResponse},
+};
+use phase1::helpers::{curve_from_str, proving_system_from_str};
+
+use wasm_bindgen::prelude::*;
+
+/// The parameters of the ceremony a contribution was made to, as they are
+/// recorded in its attestation.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParametersSummary {
+    curve: String,
+    proving_system: String,
+    power: usize,
+    batch_size: usize,
+}
+
+#[wasm_bindgen]
+impl ParametersSummary {
+    /// Checks the parameters, and records the curve and proving system by
+    /// their lowercase names, so the same ceremony is always summarized the same way.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        curve_kind: &str,
+        proving_system: &str,
+        power: usize,
+        batch_size: usize,
+    ) -> Result<ParametersSummary, Phase1Error> {
+        curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+        let parsed_proving_system =
+            proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        validate_parameters(parsed_proving_system, power, batch_size, None)?;
+
+        Ok(ParametersSummary {
+            curve: curve_kind.to_lowercase(),
+            proving_system: proving_system.to_lowercase(),
+            power,
+            batch_size,
+        })
+    }
+}
+
+/// The record of a contribution a coordinator publishes, which links the
+/// accumulator it was made to, the response, and the ceremony parameters.
+///
+/// An attestation isn't signed, but `to_bytes` is a stable encoding of it
+/// that can be signed externally. The fields are declared in alphabetical
+/// order, so its JSON has sorted keys and no whitespace, with the hashes
+/// and the public key as lowercase hex strings.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    batch_size: usize,
+    contribution_hash: String,
+    curve: String,
+    power: usize,
+    previous_hash: String,
+    proving_system: String,
+    public_key: String,
+}
+
+#[wasm_bindgen]
+impl Attestation {
+    /// Serializes the attestation to its canonical JSON.
+    pub fn to_json(&self) -> Result<String, Phase1Error> {
+        serde_json::to_string(self).map_err(|e| Phase1Error::Encoding(e.to_string()))
+    }
+
+    /// The canonical JSON of the attestation as UTF-8, which is what should be signed.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Phase1Error> {
+        Ok(self.to_json()?.into_bytes())
+    }
+
+    /// Deserializes an attestation serialized by `to_json`.
+    pub fn from_json(s: &str) -> Result<Attestation, Phase1Error> {
+        serde_json::from_str(s).map_err(|e| Phase1Error::Encoding(e.to_string()))
+    }
+}
+
+/// Builds the attestation of the contribution `response` to the ceremony `parameters`.
+#[wasm_bindgen]
+pub fn build_attestation(response: &ContributionResponse, parameters: &ParametersSummary) -> Attestation {
+    Attestation {
+        batch_size: parameters.batch_size,
+        contribution_hash: hex::encode(response.contribution_hash()),
+        curve: parameters.curve.clone(),
+        power: parameters.power,
+        previous_hash: hex::encode(response.current_accumulator_hash()),
+        proving_system: parameters.proving_system.clone(),
+        public_key: hex::encode(response.public_key()),
+    }
+}
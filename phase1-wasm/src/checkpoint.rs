@@ -0,0 +1,156 @@
+use crate::errors::Phase1Error;
+use phase1::{PrivateKey, PublicKey};
+
+use snarkvm_curves::PairingEngine;
+use std::convert::TryInto;
+use wasm_bindgen::prelude::*;
+
+/// The version of the checkpoint encoding, which is its first byte.
+const CHECKPOINT_VERSION: u8 = 1;
+
+/// How far a contribution got before its checkpoint was taken.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointStage {
+    /// The keypair was generated, but the challenge wasn't transformed yet.
+    KeyGenerated = 1,
+    /// The challenge was transformed, but the public key wasn't written yet.
+    Computed = 2,
+}
+
+/// The intermediate state of a contribution, from which it can be resumed
+/// without generating a new keypair or redoing the computation.
+///
+/// A checkpoint contains the private key of the contribution. Anyone who
+/// obtains it can recover the toxic waste, so it must be encrypted at rest,
+/// and destroyed along with the key once the contribution is complete. It
+/// isn't `Clone`, so no copy of the key outlives the checkpoint's own drop.
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    stage: CheckpointStage,
+    current_accumulator_hash: Vec<u8>,
+    public_key: Vec<u8>,
+    private_key: Vec<u8>,
+    /// The transformed accumulator, which is only kept once it is computed.
+    response: Vec<u8>,
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+
+        self.private_key.zeroize();
+    }
+}
+
+#[wasm_bindgen]
+impl Checkpoint {
+    /// Decodes a checkpoint encoded by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Checkpoint, Phase1Error> {
+        let invalid = |reason: &str| Phase1Error::Checkpoint(reason.to_string());
+
+        let (header, mut rest) = match bytes {
+            [version, stage, rest @ ..] => ((*version, *stage), rest),
+            _ => return Err(invalid("the checkpoint is truncated")),
+        };
+        if header.0 != CHECKPOINT_VERSION {
+            return Err(invalid("the checkpoint version is not supported"));
+        }
+        let stage = match header.1 {
+            1 => CheckpointStage::KeyGenerated,
+            2 => CheckpointStage::Computed,
+            _ => return Err(invalid("the checkpoint stage is unknown")),
+        };
+
+        let mut field = || -> Result<Vec<u8>, Phase1Error> {
+            if rest.len() < 8 {
+                return Err(invalid("the checkpoint is truncated"));
+            }
+            let (length, tail) = rest.split_at(8);
+            let length = u64::from_le_bytes(length.try_into().unwrap()) as usize;
+            if tail.len() < length {
+                return Err(invalid("the checkpoint is truncated"));
+            }
+            let (value, tail) = tail.split_at(length);
+            rest = tail;
+            Ok(value.to_vec())
+        };
+
+        Ok(Checkpoint {
+            stage,
+            current_accumulator_hash: field()?,
+            public_key: field()?,
+            private_key: field()?,
+            response: field()?,
+        })
+    }
+
+    /// Encodes the checkpoint, so it can be stored and resumed from later.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![CHECKPOINT_VERSION, self.stage as u8];
+        for field in &[
+            &self.current_accumulator_hash,
+            &self.public_key,
+            &self.private_key,
+            &self.response,
+        ] {
+            bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn stage(&self) -> CheckpointStage {
+        self.stage
+    }
+
+    /// The hash of the challenge the checkpointed contribution is to.
+    #[wasm_bindgen(getter)]
+    pub fn current_accumulator_hash(&self) -> Vec<u8> {
+        self.current_accumulator_hash.clone()
+    }
+}
+
+impl Checkpoint {
+    pub(crate) fn new<E: PairingEngine>(
+        stage: CheckpointStage,
+        current_accumulator_hash: &[u8],
+        public_key: &PublicKey<E>,
+        private_key: &PrivateKey<E>,
+        response: &[u8],
+    ) -> Result<Checkpoint, Phase1Error> {
+        Ok(Checkpoint {
+            stage,
+            current_accumulator_hash: current_accumulator_hash.to_vec(),
+            public_key: public_key
+                .to_bytes()
+                .map_err(|e| Phase1Error::Checkpoint(e.to_string()))?,
+            private_key: private_key
+                .to_bytes()
+                .map_err(|e| Phase1Error::Checkpoint(e.to_string()))?,
+            response: match stage {
+                CheckpointStage::KeyGenerated => vec![],
+                CheckpointStage::Computed => response.to_vec(),
+            },
+        })
+    }
+
+    /// Returns the keypair of the checkpointed contribution.
+    pub(crate) fn keypair<E: PairingEngine>(&self) -> Result<(PublicKey<E>, PrivateKey<E>), Phase1Error> {
+        let public_key = PublicKey::from_bytes(&self.public_key).map_err(|e| Phase1Error::Checkpoint(e.to_string()))?;
+        let private_key =
+            PrivateKey::from_bytes(&self.private_key).map_err(|e| Phase1Error::Checkpoint(e.to_string()))?;
+        Ok((public_key, private_key))
+    }
+
+    /// Returns the transformed accumulator, if the checkpoint was taken after it was computed.
+    pub(crate) fn response(&self) -> Option<&[u8]> {
+        match self.stage {
+            CheckpointStage::KeyGenerated => None,
+            CheckpointStage::Computed => Some(&self.response),
+        }
+    }
+}
@@ -0,0 +1,537 @@
+//! The contribution itself, without progress reporting, cancellation,
+//! checkpoints or anything else of the browser.
+//!
+//! Apart from the crate's `Phase1Error`, which doesn't depend on `wasm_bindgen`
+//! either, this only depends on `phase1`, `setup_utils`, the snarkVM curves and
+//! fields, `rand`, `sha2` and `subtle`, so it can be used where `wasm_bindgen`
+//! and the worker pool can't. `Phase1WASM` and `Phase1Pool` contribute with the
+//! steps of this module too.
+//!
+//! ```ignore
+//! let mut response = vec![];
+//! let contribution = contribution_core::contribute(
+//!     &challenge,
+//!     &parameters,
+//!     UseCompression::No,
+//!     UseCompression::Yes,
+//!     CheckForCorrectness::No,
+//!     rand::thread_rng(),
+//!     &mut response,
+//! )?;
+//! ```
+
+use crate::phase1_error::Phase1Error;
+use phase1::{Phase1, Phase1Parameters, PrivateKey, PublicKey};
+use setup_utils::{calculate_hash, compute_g2_s, CheckForCorrectness, Deserializer, Serializer, UseCompression};
+
+#[cfg(not(feature = "verify-only"))]
+use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha256};
+use snarkvm_curves::PairingEngine;
+use std::ops::Mul;
+use subtle::ConstantTimeEq;
+
+/// The hashes and public key of a contribution, whose response was written
+/// into the caller's buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    /// The hash of the challenge that was contributed to.
+    pub current_accumulator_hash: Vec<u8>,
+    /// The hash of the response.
+    pub contribution_hash: Vec<u8>,
+    /// The serialized public key, which is also written at the end of the response.
+    pub public_key: Vec<u8>,
+}
+
+/// The bytes a response is filled with before the challenge is transformed
+/// into it. The hash of the challenge is always written at the start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseFill {
+    /// The repeated hash of the challenge.
+    AccumulatorHash,
+    Zero,
+    /// The repeated byte.
+    Pattern(u8),
+}
+
+impl Default for ResponseFill {
+    fn default() -> Self {
+        ResponseFill::AccumulatorHash
+    }
+}
+
+/// The length of the hashes at the start of a response, which its public key signs.
+const TRANSCRIPT_HASH_LENGTH: usize = 64;
+
+/// The hash the challenge and response hashes of a contribution are computed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// BLAKE2b-512, as `setup_utils::calculate_hash` computes it.
+    Blake2b,
+    /// SHA-256, for ceremonies which standardize on it for interop with other tooling.
+    Sha256,
+}
+
+impl Default for HashAlgorithm {
+    fn default() -> Self {
+        HashAlgorithm::Blake2b
+    }
+}
+
+impl HashAlgorithm {
+    /// The length in bytes of the digests of the algorithm.
+    pub fn digest_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Blake2b => 64,
+            HashAlgorithm::Sha256 => 32,
+        }
+    }
+
+    /// Hashes `bytes` into a digest of `digest_len` bytes.
+    pub fn hash(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Blake2b => calculate_hash(bytes).to_vec(),
+            HashAlgorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+        }
+    }
+}
+
+/// Returns the hash recorded at the start of a challenge or response for the
+/// digest `digest`, which is also the hash its keypair is generated for. The
+/// format holds 64 bytes there whatever the hash, so a shorter digest is
+/// followed by zeros, which are compared along with the digest.
+pub fn transcript_hash(digest: &[u8]) -> Vec<u8> {
+    let mut hash = digest.to_vec();
+    hash.resize(TRANSCRIPT_HASH_LENGTH, 0);
+    hash
+}
+
+/// Compares two hashes in time which only depends on their lengths, so the
+/// hash links of a response are checked without revealing where they differ.
+/// Hashes of different lengths are never equal.
+pub fn hashes_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Returns the length of a challenge for `parameters`, serialized with `compression`.
+pub fn expected_challenge_len<E: PairingEngine>(
+    parameters: &Phase1Parameters<E>,
+    compression: UseCompression,
+) -> usize {
+    parameters.get_length(compression)
+}
+
+/// Returns the length of a response for `parameters`, including its public key.
+pub fn response_len<E: PairingEngine>(parameters: &Phase1Parameters<E>, compressed_output: UseCompression) -> usize {
+    match compressed_output {
+        UseCompression::Yes => parameters.contribution_size,
+        UseCompression::No => parameters.accumulator_size + parameters.public_key_size,
+    }
+}
+
+/// The error for a challenge `actual` bytes long, when one serialized with
+/// `compressed_input` was expected. A challenge with the length of one
+/// serialized the other way was most likely passed with the wrong
+/// compression, so the error says so rather than only reporting the length.
+pub fn challenge_length_error<E: PairingEngine>(
+    parameters: &Phase1Parameters<E>,
+    compressed_input: UseCompression,
+    actual: usize,
+) -> Phase1Error {
+    let expected = expected_challenge_len(parameters, compressed_input);
+    let other_compression = match compressed_input {
+        UseCompression::Yes => UseCompression::No,
+        UseCompression::No => UseCompression::Yes,
+    };
+    if actual == expected_challenge_len(parameters, other_compression) {
+        Phase1Error::WrongCompression {
+            expected,
+            actual,
+            compressed: other_compression == UseCompression::Yes,
+        }
+    } else {
+        Phase1Error::ChallengeLength { expected, actual }
+    }
+}
+
+/// Returns the length of the public key of a contribution for `parameters`,
+/// serialized with `public_key_compression`. `parameters.public_key_size` is
+/// the length of a compressed public key, which is how it's written by default.
+pub fn public_key_len<E: PairingEngine>(
+    parameters: &Phase1Parameters<E>,
+    public_key_compression: UseCompression,
+) -> usize {
+    match public_key_compression {
+        UseCompression::Yes => parameters.public_key_size,
+        UseCompression::No => 3 * parameters.curve.g2_size + 6 * parameters.curve.g1_size,
+    }
+}
+
+/// Returns the length of a response for `parameters` like `response_len`,
+/// with its public key serialized with `public_key_compression`.
+pub fn response_len_with_public_key<E: PairingEngine>(
+    parameters: &Phase1Parameters<E>,
+    compressed_output: UseCompression,
+    public_key_compression: UseCompression,
+) -> usize {
+    response_len(parameters, compressed_output) - parameters.public_key_size
+        + public_key_len(parameters, public_key_compression)
+}
+
+/// Checks that `challenge` has the length of a challenge serialized with
+/// `compressed_input`, and that its elements are valid as `check_input` says.
+pub fn check_challenge<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_input: UseCompression,
+    check_input: CheckForCorrectness,
+) -> Result<(), Phase1Error> {
+    if challenge.len() != expected_challenge_len(parameters, compressed_input) {
+        return Err(challenge_length_error(parameters, compressed_input, challenge.len()));
+    }
+
+    if check_input != CheckForCorrectness::No {
+        Phase1::deserialize(challenge, compressed_input, check_input, parameters)
+            .map_err(|e| Phase1Error::InvalidChallenge(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Fills `response` with `length` bytes as `fill` says, starting with `hash`
+/// to link the response to the challenge.
+pub fn fill_response(response: &mut Vec<u8>, hash: &[u8], length: usize, fill: ResponseFill) {
+    response.clear();
+    response.reserve(length);
+    let byte = match fill {
+        ResponseFill::AccumulatorHash => {
+            while response.len() < length {
+                let remaining = std::cmp::min(hash.len(), length - response.len());
+                response.extend_from_slice(&hash[..remaining]);
+            }
+            return;
+        }
+        ResponseFill::Zero => 0,
+        ResponseFill::Pattern(byte) => byte,
+    };
+    response.resize(length, byte);
+    let header_length = std::cmp::min(hash.len(), length);
+    response[..header_length].copy_from_slice(&hash[..header_length]);
+}
+
+/// The private key of a contribution, which holds the toxic waste. With the
+/// `zeroize` feature, its secrets are overwritten with zeros when it is
+/// dropped, so they don't linger in memory after the contribution.
+pub struct SecretKey<E: PairingEngine>(PrivateKey<E>);
+
+impl<E: PairingEngine> From<PrivateKey<E>> for SecretKey<E> {
+    fn from(private_key: PrivateKey<E>) -> Self {
+        SecretKey(private_key)
+    }
+}
+
+impl<E: PairingEngine> std::ops::Deref for SecretKey<E> {
+    type Target = PrivateKey<E>;
+
+    fn deref(&self) -> &PrivateKey<E> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: PairingEngine> Drop for SecretKey<E> {
+    fn drop(&mut self) {
+        zeroize_private_key(&mut self.0);
+    }
+}
+
+/// Overwrites the secrets of `private_key` with the element zero, in a way the
+/// compiler doesn't optimize away. `E::Fr` doesn't implement `Zeroize`, so this
+/// writes it the way `zeroize` writes its own types: volatile, then fenced.
+#[cfg(feature = "zeroize")]
+pub fn zeroize_private_key<E: PairingEngine>(private_key: &mut PrivateKey<E>) {
+    use snarkvm_fields::Zero;
+    use std::sync::atomic::{compiler_fence, Ordering};
+
+    for secret in [&mut private_key.tau, &mut private_key.alpha, &mut private_key.beta] {
+        // `secret` is a valid, aligned and exclusive reference, and zero is a
+        // valid element, so the write is sound whatever the field type holds.
+        unsafe { std::ptr::write_volatile(secret, E::Fr::zero()) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Generates the keypair of a contribution to the challenge hashed to `current_accumulator_hash`.
+#[cfg(not(feature = "verify-only"))]
+pub fn generate_keypair<E: PairingEngine + Sync>(
+    rng: &mut (impl Rng + CryptoRng),
+    current_accumulator_hash: &[u8],
+) -> Result<(PublicKey<E>, PrivateKey<E>), Phase1Error> {
+    Phase1::key_generation(rng, current_accumulator_hash).map_err(|_| Phase1Error::KeyGeneration)
+}
+
+/// The number of times `generate_keypair_with_attempts` generates a keypair
+/// unless told otherwise, before giving up.
+#[cfg(not(feature = "verify-only"))]
+pub const KEY_GENERATION_ATTEMPTS: u32 = 3;
+
+/// Generates the keypair of a contribution like `generate_keypair`, trying
+/// again up to `attempts` times in all if it fails, as key generation can
+/// reject a sample. Each attempt draws fresh randomness from `rng`, and the
+/// error of the last one is returned. At least one attempt is made.
+#[cfg(not(feature = "verify-only"))]
+pub fn generate_keypair_with_attempts<E: PairingEngine + Sync>(
+    rng: &mut (impl Rng + CryptoRng),
+    current_accumulator_hash: &[u8],
+    attempts: u32,
+) -> Result<(PublicKey<E>, PrivateKey<E>), Phase1Error> {
+    retry_with_rng(rng, attempts, |rng| generate_keypair(rng, current_accumulator_hash))
+}
+
+/// Calls `generate` with `rng` until it succeeds, up to `attempts` times.
+#[cfg(not(feature = "verify-only"))]
+pub(crate) fn retry_with_rng<R: Rng, T>(
+    rng: &mut R,
+    attempts: u32,
+    mut generate: impl FnMut(&mut R) -> Result<T, Phase1Error>,
+) -> Result<T, Phase1Error> {
+    let mut attempt = 1;
+    loop {
+        match generate(rng) {
+            Err(_) if attempt < attempts => attempt += 1,
+            result => return result,
+        }
+    }
+}
+
+/// Checks that `public_key` and `private_key` are a keypair generated for the
+/// challenge hashed to `current_accumulator_hash`. The public key doesn't
+/// record the hash, but each of its G2 points is the secret times a point
+/// hashed from it, so a keypair for another challenge doesn't match.
+pub fn check_keypair<E: PairingEngine>(
+    public_key: &PublicKey<E>,
+    private_key: &PrivateKey<E>,
+    current_accumulator_hash: &[u8],
+) -> Result<(), Phase1Error> {
+    let secrets = [
+        (&public_key.tau_g1, &public_key.tau_g2, private_key.tau),
+        (&public_key.alpha_g1, &public_key.alpha_g2, private_key.alpha),
+        (&public_key.beta_g1, &public_key.beta_g2, private_key.beta),
+    ];
+    for (personalization, &((g1_s, g1_s_x), g2_s_x, x)) in secrets.iter().enumerate() {
+        let g2_s = compute_g2_s::<E>(current_accumulator_hash, g1_s, g1_s_x, personalization as u8)
+            .map_err(|_| Phase1Error::KeypairMismatch)?;
+        if (*g1_s).mul(x) != *g1_s_x || g2_s.mul(x) != *g2_s_x {
+            return Err(Phase1Error::KeypairMismatch);
+        }
+    }
+    Ok(())
+}
+
+/// Transforms `challenge` with `private_key` into `response`, which must
+/// already have the length of a response.
+pub fn compute<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &mut [u8],
+    compressed_input: UseCompression,
+    compressed_output: UseCompression,
+    check_input: CheckForCorrectness,
+    private_key: &PrivateKey<E>,
+    parameters: &Phase1Parameters<E>,
+) -> Result<(), Phase1Error> {
+    Phase1::computation(
+        challenge,
+        response,
+        compressed_input,
+        compressed_output,
+        check_input,
+        private_key,
+        parameters,
+    )
+    .map_err(|e| Phase1Error::Computation(e.to_string()))
+}
+
+/// Writes `public_key` at the end of `response`, and returns its serialized
+/// bytes. When `strict`, the key is read back and checked to match.
+pub fn write_public_key<E: PairingEngine>(
+    response: &mut [u8],
+    public_key: &PublicKey<E>,
+    compressed_output: UseCompression,
+    parameters: &Phase1Parameters<E>,
+    strict: bool,
+) -> Result<Vec<u8>, Phase1Error> {
+    write_public_key_with_compression(
+        response,
+        public_key,
+        compressed_output,
+        UseCompression::Yes,
+        parameters,
+        strict,
+    )
+}
+
+/// Writes `public_key` like `write_public_key`, serialized with
+/// `public_key_compression`, which can differ from the compression of the
+/// accumulator for verifiers which expect an uncompressed public key.
+/// `response` must be `response_len_with_public_key` long.
+pub fn write_public_key_with_compression<E: PairingEngine>(
+    response: &mut [u8],
+    public_key: &PublicKey<E>,
+    compressed_output: UseCompression,
+    public_key_compression: UseCompression,
+    parameters: &Phase1Parameters<E>,
+    strict: bool,
+) -> Result<Vec<u8>, Phase1Error> {
+    let public_key_position = response_len(parameters, compressed_output) - parameters.public_key_size;
+    match public_key_compression {
+        UseCompression::Yes => public_key.write(response, compressed_output, parameters),
+        UseCompression::No => write_uncompressed_public_key(public_key)
+            .map(|bytes| response[public_key_position..public_key_position + bytes.len()].copy_from_slice(&bytes)),
+    }
+    .map_err(|e| Phase1Error::PublicKeyWrite(e.to_string()))?;
+
+    if strict {
+        match read_public_key(response, compressed_output, public_key_compression, parameters) {
+            Ok(written) if written == *public_key => {}
+            _ => return Err(Phase1Error::PublicKeySelfCheck),
+        }
+    }
+
+    Ok(response[public_key_position..].to_vec())
+}
+
+/// Reads the public key at the end of `response`, serialized with `public_key_compression`.
+pub fn read_public_key<E: PairingEngine>(
+    response: &[u8],
+    compressed_output: UseCompression,
+    public_key_compression: UseCompression,
+    parameters: &Phase1Parameters<E>,
+) -> Result<PublicKey<E>, Phase1Error> {
+    let read = match public_key_compression {
+        UseCompression::Yes => PublicKey::<E>::read(response, compressed_output, parameters),
+        UseCompression::No => {
+            let public_key_position = response_len(parameters, compressed_output) - parameters.public_key_size;
+            read_uncompressed_public_key(&response[public_key_position..])
+        }
+    };
+    read.map_err(|e| Phase1Error::PublicKeyRead(e.to_string()))
+}
+
+/// Serializes the elements of `public_key` uncompressed, in the order the
+/// compressed key is serialized in.
+fn write_uncompressed_public_key<E: PairingEngine>(public_key: &PublicKey<E>) -> setup_utils::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    for g1 in &[
+        public_key.tau_g1.0,
+        public_key.tau_g1.1,
+        public_key.alpha_g1.0,
+        public_key.alpha_g1.1,
+        public_key.beta_g1.0,
+        public_key.beta_g1.1,
+    ] {
+        bytes.write_element(g1, UseCompression::No)?;
+    }
+    for g2 in &[public_key.tau_g2, public_key.alpha_g2, public_key.beta_g2] {
+        bytes.write_element(g2, UseCompression::No)?;
+    }
+    Ok(bytes)
+}
+
+fn read_uncompressed_public_key<E: PairingEngine>(mut bytes: &[u8]) -> setup_utils::Result<PublicKey<E>> {
+    let mut g1 = || Deserializer::read_element::<E::G1Affine>(&mut bytes, UseCompression::No, CheckForCorrectness::No);
+    let (tau_g1, alpha_g1, beta_g1) = ((g1()?, g1()?), (g1()?, g1()?), (g1()?, g1()?));
+    let mut g2 = || Deserializer::read_element::<E::G2Affine>(&mut bytes, UseCompression::No, CheckForCorrectness::No);
+    Ok(PublicKey {
+        tau_g1,
+        alpha_g1,
+        beta_g1,
+        tau_g2: g2()?,
+        alpha_g2: g2()?,
+        beta_g2: g2()?,
+    })
+}
+
+/// Contributes to `challenge`, writing the response into `response`, which
+/// is cleared and resized to the length of a response first.
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_input: UseCompression,
+    compressed_output: UseCompression,
+    check_input: CheckForCorrectness,
+    mut rng: impl Rng + CryptoRng,
+    response: &mut Vec<u8>,
+) -> Result<Contribution, Phase1Error> {
+    // `Phase1::computation` panics on invalid elements, so check them up front.
+    check_challenge(challenge, parameters, compressed_input, check_input)?;
+
+    let current_accumulator_hash = calculate_hash(challenge).to_vec();
+    let length = response_len(parameters, compressed_output);
+    fill_response(response, &current_accumulator_hash, length, ResponseFill::default());
+
+    let (public_key, private_key) =
+        generate_keypair_with_attempts(&mut rng, &current_accumulator_hash, KEY_GENERATION_ATTEMPTS)?;
+    let private_key = SecretKey::from(private_key);
+    compute(
+        challenge,
+        response,
+        compressed_input,
+        compressed_output,
+        check_input,
+        &private_key,
+        parameters,
+    )?;
+    let public_key = write_public_key(response, &public_key, compressed_output, parameters, true)?;
+
+    Ok(Contribution {
+        current_accumulator_hash,
+        contribution_hash: calculate_hash(response).to_vec(),
+        public_key,
+    })
+}
+
+/// Contributes to `challenge` like `contribute`, with a keypair generated
+/// elsewhere, such as on an air-gapped device, instead of from an RNG. The
+/// keypair is checked to be one for `challenge` with `check_keypair`.
+///
+/// The contribution is only as secure as the keypair: its secrets must have
+/// been generated from trustworthy randomness, must never have been seen by
+/// anyone else, and must be destroyed after the contribution, as anyone who
+/// learns them can undo it.
+#[cfg(not(feature = "verify-only"))]
+#[allow(clippy::too_many_arguments)]
+pub fn contribute_with_keypair<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_input: UseCompression,
+    compressed_output: UseCompression,
+    check_input: CheckForCorrectness,
+    public_key: &PublicKey<E>,
+    private_key: &PrivateKey<E>,
+    response: &mut Vec<u8>,
+) -> Result<Contribution, Phase1Error> {
+    check_challenge(challenge, parameters, compressed_input, check_input)?;
+
+    let current_accumulator_hash = calculate_hash(challenge).to_vec();
+    check_keypair(public_key, private_key, &current_accumulator_hash)?;
+    let length = response_len(parameters, compressed_output);
+    fill_response(response, &current_accumulator_hash, length, ResponseFill::default());
+
+    compute(
+        challenge,
+        response,
+        compressed_input,
+        compressed_output,
+        check_input,
+        private_key,
+        parameters,
+    )?;
+    let public_key = write_public_key(response, public_key, compressed_output, parameters, true)?;
+
+    Ok(Contribution {
+        current_accumulator_hash,
+        contribution_hash: calculate_hash(response).to_vec(),
+        public_key,
+    })
+}
@@ -1,4 +1,4 @@
-use crate::{phase1::Phase1WASM, pool::WorkerProcess, requests::*, utils::*};
+use crate::{phase1::Phase1Pool, requests::*, utils::*};
 use js_sys::{Function, Promise};
 use rand::{CryptoRng, Rng};
 use setup1_shared::structures::LockResponse;
@@ -35,13 +35,13 @@ pub async fn contribute(server_url: String, private_key: String, confirmation_ke
     let private_key = PrivateKey::from_str(&private_key).map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
 
     join_queue(&private_key, confirmation_key, server_url.clone(), &mut rng).await?;
-    let worker_pool = WorkerProcess::new(DEFAULT_THREAD_COUNT)?;
+    let pool = Phase1Pool::new(DEFAULT_THREAD_COUNT)?;
     let seed: [u8; 32] = rng.gen();
 
     loop {
         send_heartbeat(&private_key, server_url.clone(), &mut rng).await?;
 
-        let is_finished = attempt_contribution(&private_key, server_url.clone(), &seed, &mut rng, &worker_pool).await?;
+        let is_finished = attempt_contribution(&private_key, server_url.clone(), &seed, &mut rng, &pool).await?;
 
         if is_finished {
             break;
@@ -56,7 +56,7 @@ async fn attempt_contribution<R: Rng + CryptoRng>(
     server_url: String,
     seed: &[u8],
     rng: &mut R,
-    worker_pool: &WorkerProcess,
+    pool: &Phase1Pool,
 ) -> Result<bool, JsValue> {
     let tasks_left = match get_tasks_left(private_key, server_url.clone(), rng).await {
         Ok(b) => b,
@@ -87,19 +87,22 @@ async fn attempt_contribution<R: Rng + CryptoRng>(
     web_sys::console::log_1(&format!("{} bytes", chunk_bytes.len()).into());
 
     web_sys::console::log_1(&"contributing...".into());
-    let result = Phase1WASM::contribute_chunked(
-        CURVE_KIND,
-        PROVING_SYSTEM,
-        BATCH_SIZE,
-        POWER,
-        response.chunk_id as usize,
-        CHALLENGE_SIZE,
-        seed,
-        chunk_bytes.to_vec(),
-        &worker_pool,
-        DEFAULT_THREAD_COUNT,
-    )
-    .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
+    let result = pool
+        .contribute_chunk(
+            CURVE_KIND,
+            PROVING_SYSTEM,
+            BATCH_SIZE,
+            POWER,
+            response.chunk_id as usize,
+            CHALLENGE_SIZE,
+            seed,
+            chunk_bytes.to_vec(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
     web_sys::console::log_1(&"finished!".into());
 
     web_sys::console::log_1(&"calculating hashes".into());
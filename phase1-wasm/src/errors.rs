@@ -1,3 +1,5 @@
+pub use crate::phase1_error::Phase1Error;
+
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -14,3 +16,76 @@ impl From<ContributeError> for JsValue {
         JsValue::from_str(&format!("{:?}", value))
     }
 }
+
+/// The representation of a `Phase1Error` handed to JS, carrying the
+/// discriminant of the error alongside its message, the expected and
+/// actual sizes for the errors about a size, and the index of the
+/// contribution for the errors about a transcript.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Phase1ErrorValue {
+    code: u32,
+    message: String,
+    expected: Option<usize>,
+    actual: Option<usize>,
+    index: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl Phase1ErrorValue {
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn expected(&self) -> Option<usize> {
+        self.expected
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn actual(&self) -> Option<usize> {
+        self.actual
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+impl From<Phase1Error> for Phase1ErrorValue {
+    fn from(value: Phase1Error) -> Self {
+        let (expected, actual) = match value {
+            Phase1Error::ChallengeLength { expected, actual }
+            | Phase1Error::ResponseLength { expected, actual }
+            | Phase1Error::ChunkCountMismatch { expected, actual }
+            | Phase1Error::DownloadLength { expected, actual }
+            | Phase1Error::WrongCompression { expected, actual, .. } => (Some(expected), Some(actual)),
+            Phase1Error::PowerTooLarge { requested, max } => (Some(max), Some(requested)),
+            _ => (None, None),
+        };
+        let index = match value {
+            Phase1Error::TranscriptContribution { index, .. } => Some(index),
+            _ => None,
+        };
+        Phase1ErrorValue {
+            code: value.code(),
+            message: value.to_string(),
+            expected,
+            actual,
+            index,
+        }
+    }
+}
+
+impl From<Phase1Error> for JsValue {
+    fn from(value: Phase1Error) -> Self {
+        Phase1ErrorValue::from(value).into()
+    }
+}
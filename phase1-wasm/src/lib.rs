@@ -1,12 +1,14 @@
 #[macro_use]
 extern crate serde_derive;
 
+#[macro_use]
+mod macros;
+
 cfg_if::cfg_if! {
-    if #[cfg(not(test))] {
-        #[cfg(feature = "wasm")]
+    if #[cfg(all(not(test), not(feature = "verify-only")))] {
+        #[cfg(all(feature = "wasm", not(feature = "single_threaded")))]
         mod contributor;
-        #[cfg(feature = "wasm")]
-        mod errors;
+        #[cfg(not(feature = "single_threaded"))]
         mod pool;
         #[cfg(feature = "wasm")]
         mod requests;
@@ -17,6 +19,17 @@ cfg_if::cfg_if! {
     }
 }
 
+mod attestation;
+#[cfg(not(feature = "verify-only"))]
+mod checkpoint;
+pub mod contribution_core;
+mod errors;
+#[cfg(all(not(target_arch = "wasm32"), feature = "thread_pool"))]
+pub mod native;
+#[cfg(feature = "nodejs")]
+mod node;
 mod phase1;
-#[cfg(test)]
+mod phase1_error;
+mod rate_limiter;
+#[cfg(all(test, not(feature = "single_threaded"), not(feature = "verify-only")))]
 mod tests;
@@ -0,0 +1,52 @@
+/// Evaluates `$body` with `$engine` standing for the `PairingEngine` of the
+/// `CurveKind` `$curve_kind`, which reads like a closure generic over the
+/// curve. Every entry point dispatches on the curve with this, so they all
+/// support the same curves, and supporting another curve is adding its arm here.
+///
+/// Each curve is only compiled in with its feature, so a build for one curve
+/// leaves out the code of the other. Dispatching on a curve which isn't
+/// compiled in returns `Phase1Error::CurveNotCompiled` from the enclosing
+/// function, so it must return a `Result` whose error converts from it.
+///
+/// ```ignore
+/// let parameters = dispatch_curve!(curve_kind, |E| ParameterSizes::from(&get_parameters::<E>(
+///     proving_system,
+///     power,
+///     batch_size,
+///     None,
+///     None,
+/// )?));
+/// ```
+macro_rules! dispatch_curve {
+    ($curve_kind:expr, |$engine:ident| $body:expr) => {
+        match $curve_kind {
+            #[cfg(feature = "curve-bls12_377")]
+            ::phase1::helpers::CurveKind::Bls12_377 => {
+                type $engine = ::snarkvm_curves::bls12_377::Bls12_377;
+                $body
+            }
+            #[cfg(feature = "curve-bw6_761")]
+            ::phase1::helpers::CurveKind::BW6 => {
+                type $engine = ::snarkvm_curves::bw6_761::BW6_761;
+                $body
+            }
+            #[allow(unreachable_patterns)]
+            curve_kind => return Err($crate::errors::Phase1Error::CurveNotCompiled(format!("{:?}", curve_kind)).into()),
+        }
+    };
+}
+
+/// Evaluates `$body` with `$parameters` bound to the `Phase1Parameters` held
+/// by the `CurveParameters` `$curve_parameters`, whatever its curve, as
+/// `dispatch_curve!` does for a `CurveKind`. A `CurveParameters` only holds
+/// parameters of a curve compiled in, so this can't fail.
+macro_rules! dispatch_parameters {
+    ($curve_parameters:expr, |$parameters:ident| $body:expr) => {
+        match $curve_parameters {
+            #[cfg(feature = "curve-bls12_377")]
+            $crate::phase1::CurveParameters::Bls12_377($parameters) => $body,
+            #[cfg(feature = "curve-bw6_761")]
+            $crate::phase1::CurveParameters::BW6($parameters) => $body,
+        }
+    };
+}
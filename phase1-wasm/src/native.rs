@@ -0,0 +1,560 @@
+//! Contribution and verification outside the browser, for native CLIs and servers.
+//!
+//! The computation is the same as in `Phase1WASM`, but it runs on rayon's
+//! global thread pool instead of web workers, and the caller provides the RNG.
+
+pub use crate::{
+    errors::Phase1Error,
+    phase1::{ContributionResponse, ResponseFill, SerializationOptions},
+};
+
+use crate::{
+    contribution_core,
+    phase1::{
+        expected_challenge_len, get_parameters_with_max_power, verify_response_with_options, MAX_SUPPORTED_POWER,
+    },
+};
+#[cfg(not(feature = "verify-only"))]
+use crate::{
+    contribution_core::Contribution,
+    phase1::{contribute_challenge_with_options, ContributionOptions},
+};
+use phase1::{helpers::CurveKind, Phase1Parameters, ProvingSystem};
+use setup_utils::calculate_hash;
+
+use blake2::{Blake2b, Digest};
+use lru::LruCache;
+#[cfg(not(feature = "verify-only"))]
+use memmap::MmapOptions;
+#[cfg(not(feature = "verify-only"))]
+use rand::{CryptoRng, Rng};
+use rayon::prelude::*;
+use snarkvm_curves::PairingEngine;
+use std::sync::Mutex;
+#[cfg(not(feature = "verify-only"))]
+use std::{
+    fs::{File, OpenOptions},
+    path::Path,
+};
+
+/// Contributes to the challenges of one ceremony.
+///
+/// ```ignore
+/// let contributor = NativeContributor::new(CurveKind::Bls12_377, ProvingSystem::Groth16, power, batch_size)
+///     .serialization(SerializationOptions::new(false, true, false));
+/// let result = contributor.contribute_full(&challenge, rand::thread_rng())?;
+/// ```
+#[cfg(not(feature = "verify-only"))]
+#[derive(Clone, Copy, Debug)]
+pub struct NativeContributor {
+    curve_kind: CurveKind,
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    serialization: SerializationOptions,
+    strict: bool,
+    fill: ResponseFill,
+    max_power: usize,
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl NativeContributor {
+    pub fn new(curve_kind: CurveKind, proving_system: ProvingSystem, power: usize, batch_size: usize) -> Self {
+        Self {
+            curve_kind,
+            proving_system,
+            power,
+            batch_size,
+            serialization: SerializationOptions::default(),
+            strict: true,
+            fill: ResponseFill::default(),
+            max_power: MAX_SUPPORTED_POWER,
+        }
+    }
+
+    /// Sets how challenges are read and responses are written.
+    pub fn serialization(mut self, serialization: SerializationOptions) -> Self {
+        self.serialization = serialization;
+        self
+    }
+
+    /// Sets whether the public key written to each response is read back and checked.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets what each response is filled with before it is computed, which doesn't change the response.
+    pub fn fill(mut self, fill: ResponseFill) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Sets the largest power of a full contribution, which is `MAX_SUPPORTED_POWER`
+    /// by default so as to fit in WASM memory. A server with more memory can raise
+    /// it up to `MAX_POWER`.
+    pub fn max_power(mut self, max_power: usize) -> Self {
+        self.max_power = max_power;
+        self
+    }
+
+    /// Contributes to the challenge of the full accumulator.
+    pub fn contribute_full(
+        &self,
+        challenge: &[u8],
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        dispatch_curve!(self.curve_kind, |E| contribute_challenge_with_options(
+            challenge,
+            &self.parameters::<E>(None, None)?,
+            rng,
+            self.options(),
+        ))
+    }
+
+    /// Contributes to the challenge of the chunk `chunk_index`, with chunks of `chunk_size` powers.
+    pub fn contribute_chunked(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+        challenge: &[u8],
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let (chunk_index, chunk_size) = (Some(chunk_index), Some(chunk_size));
+        dispatch_curve!(self.curve_kind, |E| contribute_challenge_with_options(
+            challenge,
+            &self.parameters::<E>(chunk_index, chunk_size)?,
+            rng,
+            self.options(),
+        ))
+    }
+
+    /// Contributes to the challenge of the full accumulator in the file at
+    /// `challenge_path`, writing the response to a new file at `response_path`.
+    /// Both files are memory-mapped, so neither is held in memory, and the
+    /// `response` of the returned `ContributionResponse` is left empty.
+    pub fn contribute_full_from_path(
+        &self,
+        challenge_path: &Path,
+        response_path: &Path,
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        dispatch_curve!(self.curve_kind, |E| self.contribute_file(
+            challenge_path,
+            response_path,
+            &self.parameters::<E>(None, None)?,
+            rng,
+        ))
+    }
+
+    /// Contributes to the challenge of the chunk `chunk_index` like
+    /// `contribute_full_from_path`, with chunks of `chunk_size` powers.
+    pub fn contribute_chunked_from_path(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+        challenge_path: &Path,
+        response_path: &Path,
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let (chunk_index, chunk_size) = (Some(chunk_index), Some(chunk_size));
+        dispatch_curve!(self.curve_kind, |E| self.contribute_file(
+            challenge_path,
+            response_path,
+            &self.parameters::<E>(chunk_index, chunk_size)?,
+            rng,
+        ))
+    }
+
+    /// The parameters of the chunk `chunk_index` when given with `chunk_size`,
+    /// and of the full accumulator otherwise.
+    fn parameters<E: PairingEngine>(
+        &self,
+        chunk_index: Option<usize>,
+        chunk_size: Option<usize>,
+    ) -> Result<Phase1Parameters<E>, Phase1Error> {
+        get_parameters_with_max_power(
+            self.proving_system,
+            self.power,
+            self.batch_size,
+            chunk_index,
+            chunk_size,
+            self.max_power,
+        )
+    }
+
+    fn contribute_file<E: PairingEngine + Sync>(
+        &self,
+        challenge_path: &Path,
+        response_path: &Path,
+        parameters: &Phase1Parameters<E>,
+        mut rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let SerializationOptions {
+            compressed_input,
+            compressed_output,
+            check_input,
+        } = self.serialization;
+        let io = |e: std::io::Error| Phase1Error::Io(e.to_string());
+
+        // An empty file can't be mapped, so check the length before mapping it.
+        let challenge_file = File::open(challenge_path).map_err(io)?;
+        let challenge_length = challenge_file.metadata().map_err(io)?.len() as usize;
+        if challenge_length != expected_challenge_len(parameters, compressed_input) {
+            return Err(contribution_core::challenge_length_error(
+                parameters,
+                compressed_input,
+                challenge_length,
+            ));
+        }
+        let challenge = unsafe { MmapOptions::new().map(&challenge_file) }.map_err(io)?;
+        contribution_core::check_challenge(&challenge, parameters, compressed_input, check_input)?;
+
+        let response_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(response_path)
+            .map_err(io)?;
+        response_file
+            .set_len(contribution_core::response_len(parameters, compressed_output) as u64)
+            .map_err(io)?;
+        let mut response = unsafe { MmapOptions::new().map_mut(&response_file) }.map_err(io)?;
+
+        let current_accumulator_hash = calculate_hash(&challenge).to_vec();
+        response[..current_accumulator_hash.len()].copy_from_slice(&current_accumulator_hash);
+
+        let (public_key, private_key) = contribution_core::generate_keypair_with_attempts(
+            &mut rng,
+            &current_accumulator_hash,
+            contribution_core::KEY_GENERATION_ATTEMPTS,
+        )?;
+        let private_key = contribution_core::SecretKey::from(private_key);
+        contribution_core::compute(
+            &challenge,
+            &mut response,
+            compressed_input,
+            compressed_output,
+            check_input,
+            &private_key,
+            parameters,
+        )?;
+        let public_key = contribution_core::write_public_key(
+            &mut response,
+            &public_key,
+            compressed_output,
+            parameters,
+            self.strict,
+        )?;
+        response.flush().map_err(io)?;
+
+        Ok(ContributionResponse::from_contribution(Contribution {
+            current_accumulator_hash,
+            contribution_hash: calculate_hash(&response).to_vec(),
+            public_key,
+        }))
+    }
+
+    fn options(&self) -> ContributionOptions<'static> {
+        ContributionOptions {
+            serialization: self.serialization,
+            strict: self.strict,
+            fill: self.fill,
+            ..Default::default()
+        }
+    }
+}
+
+/// The hashes of a challenge and of a response to it.
+type CacheKey = (Vec<u8>, Vec<u8>);
+
+/// The verdicts of the verifications a `NativeVerifier` has run, keyed by the
+/// hashes of the challenge and the response, so the same bytes hit the cache
+/// wherever they were read from. The least recently used verdict is evicted
+/// once `capacity` verdicts are cached.
+#[derive(Debug)]
+pub struct VerificationCache {
+    verdicts: LruCache<CacheKey, bool>,
+    hits: u64,
+}
+
+impl VerificationCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            verdicts: LruCache::new(capacity),
+            hits: 0,
+        }
+    }
+
+    /// The verdict of an earlier verification of `response` on top of `challenge`, if it's still cached.
+    pub fn get(&mut self, challenge: &[u8], response: &[u8]) -> Option<bool> {
+        self.get_key(&cache_key(challenge, response))
+    }
+
+    pub fn insert(&mut self, challenge: &[u8], response: &[u8], verdict: bool) {
+        self.insert_key(cache_key(challenge, response), verdict);
+    }
+
+    /// The number of verifications which were served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    fn get_key(&mut self, key: &CacheKey) -> Option<bool> {
+        let verdict = self.verdicts.get(key).copied();
+        if verdict.is_some() {
+            self.hits += 1;
+        }
+        verdict
+    }
+
+    fn insert_key(&mut self, key: CacheKey, verdict: bool) {
+        self.verdicts.put(key, verdict);
+    }
+}
+
+fn cache_key(challenge: &[u8], response: &[u8]) -> CacheKey {
+    (calculate_hash(challenge).to_vec(), calculate_hash(response).to_vec())
+}
+
+/// Verifies the contributions to the challenges of one set of ceremony
+/// parameters, which is what makes the hashes of a challenge and a response
+/// enough to key its cache on.
+///
+/// ```ignore
+/// let verifier = NativeVerifier::new(CurveKind::Bls12_377, ProvingSystem::Groth16, power, batch_size)
+///     .chunk(chunk_index, chunk_size)
+///     .cache(1024);
+/// let is_valid = verifier.verify(&challenge, &response)?;
+/// ```
+#[derive(Debug)]
+pub struct NativeVerifier {
+    curve_kind: CurveKind,
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk: Option<(usize, usize)>,
+    serialization: SerializationOptions,
+    max_power: usize,
+    cache: Option<Mutex<VerificationCache>>,
+}
+
+impl NativeVerifier {
+    pub fn new(curve_kind: CurveKind, proving_system: ProvingSystem, power: usize, batch_size: usize) -> Self {
+        Self {
+            curve_kind,
+            proving_system,
+            power,
+            batch_size,
+            chunk: None,
+            serialization: SerializationOptions::default(),
+            max_power: MAX_SUPPORTED_POWER,
+            cache: None,
+        }
+    }
+
+    /// Verifies the contributions to the chunk `chunk_index`, with chunks of
+    /// `chunk_size` powers, instead of to the full accumulator.
+    pub fn chunk(mut self, chunk_index: usize, chunk_size: usize) -> Self {
+        self.chunk = Some((chunk_index, chunk_size));
+        self
+    }
+
+    /// Sets how challenges and responses are read.
+    pub fn serialization(mut self, serialization: SerializationOptions) -> Self {
+        self.serialization = serialization;
+        self
+    }
+
+    /// Sets the largest power of a full contribution, like `NativeContributor::max_power`.
+    pub fn max_power(mut self, max_power: usize) -> Self {
+        self.max_power = max_power;
+        self
+    }
+
+    /// Caches the verdicts of up to `capacity` verifications, so a response
+    /// which is received again isn't verified again.
+    pub fn cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Mutex::new(VerificationCache::new(capacity)));
+        self
+    }
+
+    /// The number of verifications which were served from the cache, which is
+    /// zero without one.
+    pub fn cache_hits(&self) -> u64 {
+        self.cache
+            .as_ref()
+            .map_or(0, |cache| cache.lock().expect("the cache lock was poisoned").hits())
+    }
+
+    /// Verifies that `response` is a valid contribution on top of `challenge`.
+    /// Only verdicts are cached, so a response which failed with an error is
+    /// checked again.
+    pub fn verify(&self, challenge: &[u8], response: &[u8]) -> Result<bool, Phase1Error> {
+        self.verify_with_key(challenge, response, || cache_key(challenge, response))
+    }
+
+    /// Starts verifying a response to `challenge` which is pushed to the
+    /// returned `StreamingVerifier` as it's read, from a file or the network.
+    pub fn streaming<'a>(&'a self, challenge: &'a [u8]) -> Result<StreamingVerifier<'a>, Phase1Error> {
+        let (response_length, hash_size) = dispatch_curve!(self.curve_kind, |E| {
+            let parameters = self.parameters::<E>()?;
+            let compressed_input = self.serialization.compressed_input;
+            if challenge.len() != expected_challenge_len(&parameters, compressed_input) {
+                return Err(contribution_core::challenge_length_error(
+                    &parameters,
+                    compressed_input,
+                    challenge.len(),
+                ));
+            }
+            (
+                contribution_core::response_len(&parameters, self.serialization.compressed_output),
+                parameters.hash_size,
+            )
+        });
+
+        Ok(StreamingVerifier {
+            verifier: self,
+            challenge,
+            challenge_hash: calculate_hash(challenge).to_vec(),
+            response: Vec::with_capacity(response_length),
+            response_length,
+            hash_size,
+            received: 0,
+            hasher: Blake2b::default(),
+            unlinked: false,
+        })
+    }
+
+    /// Verifies like `verify`, with the cache keyed on the hashes `key` returns,
+    /// which are only computed when there is a cache.
+    fn verify_with_key(
+        &self,
+        challenge: &[u8],
+        response: &[u8],
+        key: impl FnOnce() -> CacheKey,
+    ) -> Result<bool, Phase1Error> {
+        let key = self.cache.as_ref().map(|cache| (cache, key()));
+        if let Some((cache, key)) = &key {
+            if let Some(verdict) = cache.lock().expect("the cache lock was poisoned").get_key(key) {
+                return Ok(verdict);
+            }
+        }
+
+        let verdict = dispatch_curve!(self.curve_kind, |E| verify_response_with_options(
+            challenge,
+            response,
+            &self.parameters::<E>()?,
+            self.serialization,
+        ))?;
+
+        if let Some((cache, key)) = key {
+            cache
+                .lock()
+                .expect("the cache lock was poisoned")
+                .insert_key(key, verdict);
+        }
+        Ok(verdict)
+    }
+
+    fn parameters<E: PairingEngine>(&self) -> Result<Phase1Parameters<E>, Phase1Error> {
+        let (chunk_index, chunk_size) = self
+            .chunk
+            .map_or((None, None), |(index, size)| (Some(index), Some(size)));
+        get_parameters_with_max_power(
+            self.proving_system,
+            self.power,
+            self.batch_size,
+            chunk_index,
+            chunk_size,
+            self.max_power,
+        )
+    }
+}
+
+/// Verifies a response which is pushed in pieces as it arrives, from
+/// `NativeVerifier::streaming`, with the same verdict as `NativeVerifier::verify`.
+///
+/// `Phase1::verification` checks each batch of tau_g1 against the same batch
+/// of tau_g2, alpha_g1 and beta_g1, which lie far apart in the response, and
+/// can only read the response as one slice, so the response is still buffered
+/// in full until `finish`. What streaming saves is that:
+///
+/// + the buffer is allocated once at the length of a response, rather than
+///   growing and briefly being held twice as it's reallocated
+/// + a response to another challenge is rejected after its first `hash_size`
+///   bytes, and one that is too long when the excess arrives, and neither is
+///   buffered any further
+/// + the response is hashed for the cache as it arrives, rather than after
+///   it has been read
+pub struct StreamingVerifier<'a> {
+    verifier: &'a NativeVerifier,
+    challenge: &'a [u8],
+    challenge_hash: Vec<u8>,
+    response: Vec<u8>,
+    response_length: usize,
+    hash_size: usize,
+    received: usize,
+    hasher: Blake2b,
+    /// Whether the response doesn't start with the hash of the challenge.
+    unlinked: bool,
+}
+
+impl<'a> StreamingVerifier<'a> {
+    /// Appends `bytes` to the response.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.received += bytes.len();
+        if self.unlinked || self.received > self.response_length {
+            self.response = vec![];
+            return;
+        }
+
+        let linked_before = self.response.len() >= self.hash_size;
+        self.response.extend_from_slice(bytes);
+        self.hasher.update(bytes);
+        if !linked_before
+            && self.response.len() >= self.hash_size
+            && !contribution_core::hashes_eq(&self.response[..self.hash_size], &self.challenge_hash)
+        {
+            self.unlinked = true;
+            self.response = vec![];
+        }
+    }
+
+    /// Verifies the response pushed so far, which must be the whole of it.
+    pub fn finish(self) -> Result<bool, Phase1Error> {
+        if self.received != self.response_length {
+            return Err(Phase1Error::ResponseLength {
+                expected: self.response_length,
+                actual: self.received,
+            });
+        }
+        if self.unlinked {
+            return Ok(false);
+        }
+
+        let (challenge_hash, hasher) = (self.challenge_hash, self.hasher);
+        self.verifier.verify_with_key(self.challenge, &self.response, || {
+            (challenge_hash, hasher.finalize().to_vec())
+        })
+    }
+}
+
+/// Verifies each pair of a challenge and its response with `parameters`, as
+/// `NativeVerifier::verify` does, in parallel on rayon's global thread pool.
+/// The verdicts are in the order of `pairs`.
+///
+/// The pairs are consumed, so each is dropped once it's verified and a batch
+/// only holds the pairs still waiting to be verified. The verification of a
+/// pair reads its challenge and response in place rather than copying them.
+pub fn verify_batch<E: PairingEngine + Sync>(
+    parameters: &Phase1Parameters<E>,
+    pairs: Vec<(Vec<u8>, Vec<u8>)>,
+) -> Vec<Result<bool, Phase1Error>> {
+    pairs
+        .into_par_iter()
+        .map(|(challenge, response)| {
+            verify_response_with_options(&challenge, &response, parameters, SerializationOptions::default())
+        })
+        .collect()
+}
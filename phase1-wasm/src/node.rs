@@ -0,0 +1,44 @@
+//! Glue for calling the contribution from Node.js scripts, behind the
+//! `nodejs` feature.
+//!
+//! A Node `Buffer` is a `Uint8Array`, so it can be passed as is wherever a
+//! challenge or response is taken, as a `&[u8]` or a `Vec<u8>`. The bytes
+//! returned are `Uint8Array`s, which this module hands out as `Buffer`s
+//! instead, wrapping the copy out of WASM memory rather than copying it again.
+
+use crate::phase1::ContributionResponse;
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "buffer")]
+extern "C" {
+    /// Node's `Buffer`.
+    pub type Buffer;
+
+    #[wasm_bindgen(static_method_of = Buffer, js_name = from)]
+    fn from_array_buffer(array_buffer: &js_sys::ArrayBuffer, byte_offset: u32, length: u32) -> Buffer;
+}
+
+/// Copies `bytes` out of WASM memory into a `Buffer`.
+fn to_buffer(bytes: &[u8]) -> Buffer {
+    let array = js_sys::Uint8Array::from(bytes);
+    Buffer::from_array_buffer(&array.buffer(), array.byte_offset(), array.length())
+}
+
+#[wasm_bindgen]
+impl ContributionResponse {
+    /// The response, as a `Buffer`.
+    pub fn response_buffer(&self) -> Buffer {
+        to_buffer(&self.response)
+    }
+
+    /// The hash of the response, as a `Buffer`.
+    pub fn contribution_hash_buffer(&self) -> Buffer {
+        to_buffer(&self.contribution_hash())
+    }
+
+    /// The serialized public key of the contribution, as a `Buffer`.
+    pub fn public_key_buffer(&self) -> Buffer {
+        to_buffer(&self.public_key())
+    }
+}
@@ -1,206 +1,4069 @@
+pub use crate::contribution_core::{expected_challenge_len, HashAlgorithm, ResponseFill};
+
+#[cfg(not(feature = "verify-only"))]
+use crate::{
+    checkpoint::{Checkpoint, CheckpointStage},
+    contribution_core::fill_response,
+};
+use crate::{contribution_core, errors::Phase1Error};
 use phase1::{ContributionMode, Phase1, Phase1Parameters, ProvingSystem};
 
-#[cfg(not(test))]
 use phase1::helpers::{curve_from_str, proving_system_from_str, CurveKind};
 
-use setup_utils::{calculate_hash, CheckForCorrectness, UseCompression};
-
-#[cfg(not(test))]
-use setup_utils::{derive_rng_from_seed, get_rng, user_system_randomness};
-use snarkvm_curves::PairingEngine;
+use setup_utils::{blank_hash, calculate_hash, CheckForCorrectness, Deserializer, UseCompression};
 
-#[cfg(not(test))]
-use snarkvm_curves::{bls12_377::Bls12_377, bw6_761::BW6_761};
+#[cfg(not(feature = "verify-only"))]
+use setup_utils::{derive_rng_from_seed, get_rng};
+use snarkvm_curves::{AffineCurve, PairingEngine};
+use snarkvm_fields::Zero;
 
+#[cfg(not(feature = "verify-only"))]
+use blake2::Blake2b;
+#[cfg(not(feature = "verify-only"))]
 use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha256};
+#[cfg(not(feature = "verify-only"))]
+use std::sync::atomic::AtomicU32;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use wasm_bindgen::prelude::*;
 
 pub(crate) const COMPRESSED_INPUT: UseCompression = UseCompression::No;
 pub(crate) const COMPRESSED_OUTPUT: UseCompression = UseCompression::Yes;
 pub(crate) const CHECK_INPUT_CORRECTNESS: CheckForCorrectness = CheckForCorrectness::No;
 
-// When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
-// allocator.
+/// The size of the slices a streamed response is handed out in.
+#[cfg(not(feature = "verify-only"))]
+const STREAM_SLICE_SIZE: usize = 1 << 20;
+
+/// The length in bytes of the hashes of challenges and responses.
+pub const HASH_LENGTH: usize = 64;
+
+/// The smallest power of a ceremony that `validate_parameters` accepts.
+pub const MIN_POWER: usize = 1;
+
+/// The largest power of a ceremony that `validate_parameters` accepts.
+pub const MAX_POWER: usize = 28;
+
+/// The largest power of a full contribution the entry points taking the
+/// parameters by name accept, which is the largest at which a contribution
+/// on either curve fits in WASM memory. `NativeContributor::max_power` raises
+/// it for servers with more memory.
+pub const MAX_SUPPORTED_POWER: usize = 21;
+
+/// The memory browsers reliably give a WASM module, which a full contribution
+/// must fit in by `estimate_contribution_memory`.
+const WASM_MEMORY_LIMIT: usize = 1 << 31;
+
+/// The smallest batch size `suggest_batch_size_for` suggests, below which the
+/// computation is slowed down by the overhead of each batch.
+pub const MIN_SUGGESTED_BATCH_SIZE: usize = 64;
+
+/// The largest batch size `suggest_batch_size_for` suggests, above which the
+/// computation barely gets faster.
+pub const MAX_SUGGESTED_BATCH_SIZE: usize = 1 << 16;
+
+/// The minimum length in bytes of a seed the contribution RNG is derived from.
+#[cfg(not(feature = "verify-only"))]
+pub const MIN_SEED_LENGTH: usize = 32;
+
+// The global allocator is chosen with a feature:
+//
+// + no feature - the standard library's allocator, which is recommended
+//   unless code size matters
+// + `wee_alloc` - the smallest allocator, which is only recommended for
+//   ceremonies of a power up to around 16, as it fragments badly on the
+//   allocations of larger ones
+// + `dlmalloc_alloc` - `dlmalloc`, which is recommended for ceremonies of a
+//   power above 16 when the standard library's allocator isn't an option
+#[cfg(all(feature = "wee_alloc", feature = "dlmalloc_alloc"))]
+compile_error!(
+    "the `wee_alloc` and `dlmalloc_alloc` features select different global allocators, so only one can be enabled"
+);
+
+// The `parallel` feature runs the computation on the global rayon pool,
+// which needs the web workers `single_threaded` is there to do without.
+#[cfg(all(feature = "parallel", feature = "single_threaded"))]
+compile_error!("the `single_threaded` feature contributes without threads, so it can't be enabled with `parallel`");
+
+// Without `single_threaded`, contributions run on rayon thread pools, which
+// come with the `thread_pool` feature.
+#[cfg(all(
+    not(feature = "single_threaded"),
+    not(feature = "thread_pool"),
+    not(feature = "verify-only")
+))]
+compile_error!(
+    "contributing needs the `thread_pool` feature, or `single_threaded` to contribute on the calling thread"
+);
+
+// Each curve is compiled in with its feature, to leave the code of the other
+// out of a ceremony's build, and a build without any curve can't contribute.
+#[cfg(not(any(feature = "curve-bls12_377", feature = "curve-bw6_761")))]
+compile_error!("no curve is compiled in, so at least one of `curve-bls12_377` and `curve-bw6_761` must be enabled");
+
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-#[derive(Serialize)]
+#[cfg(feature = "dlmalloc_alloc")]
+#[global_allocator]
+static ALLOC: dlmalloc::GlobalDlmalloc = dlmalloc::GlobalDlmalloc;
+
+/// The marker at the start of the JSON form of a `ContributionResponse`.
+pub const RESPONSE_FORMAT_MAGIC: &str = "aleo-setup-phase1-response";
+
+/// The version of the layout of responses, including their public keys, which
+/// is bumped whenever it changes. It's recorded alongside a response rather
+/// than in it, as `Phase1::verification` reads the response as it is.
+pub const RESPONSE_FORMAT_VERSION: u16 = 1;
+
+/// The result of a contribution. In its JSON form, from `to_json`, the byte
+/// fields are lowercase hex strings, and the format of the response is
+/// recorded, so a response of a later format isn't misread.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ContributionResponse {
+    // JSON from before the format was recorded has the first version's layout.
+    #[serde(default = "response_format_magic")]
+    format_magic: String,
+    #[serde(default = "response_format_version")]
+    format_version: u16,
+    #[serde(with = "hex_bytes")]
     current_accumulator_hash: Vec<u8>,
+    #[wasm_bindgen(skip)]
+    #[serde(with = "hex_bytes")]
     pub response: Vec<u8>,
+    #[serde(with = "hex_bytes")]
     contribution_hash: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    public_key: Vec<u8>,
+    #[serde(with = "hex_bytes::option")]
+    beacon_seed: Option<Vec<u8>>,
+    #[serde(with = "hex_bytes::option")]
+    randomness_commitment: Option<Vec<u8>>,
+    timing: Option<ContributionTiming>,
 }
 
-/// Initialize the following hooks:
-///
-/// + console error panic hook - to display panic messages in the console
-#[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn init_hooks() {
-    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+impl ContributionResponse {
+    /// Serializes the response to JSON.
+    pub fn to_json(&self) -> Result<String, Phase1Error> {
+        serde_json::to_string(self).map_err(|e| Phase1Error::Encoding(e.to_string()))
+    }
+
+    /// Deserializes a response serialized by `to_json`, rejecting one of a
+    /// format this version can't read with `Phase1Error::UnsupportedFormat`.
+    pub fn from_json(s: &str) -> Result<ContributionResponse, Phase1Error> {
+        let response: ContributionResponse =
+            serde_json::from_str(s).map_err(|e| Phase1Error::Encoding(e.to_string()))?;
+        response.check_format()?;
+        Ok(response)
+    }
+
+    /// The version of the layout of the response, which is `RESPONSE_FORMAT_VERSION`
+    /// for a response of this version.
+    #[wasm_bindgen(getter)]
+    pub fn format_version(&self) -> u16 {
+        self.format_version
+    }
+
+    /// The hash of the challenge that was contributed to.
+    #[wasm_bindgen(getter)]
+    pub fn current_accumulator_hash(&self) -> Vec<u8> {
+        self.current_accumulator_hash.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn response(&self) -> Vec<u8> {
+        self.response.clone()
+    }
+
+    /// The transformed accumulator, which is the response without the public
+    /// key at its end, for storing the two separately.
+    pub fn response_without_public_key(&self) -> Vec<u8> {
+        self.response[..self.public_key_position()].to_vec()
+    }
+
+    /// The public key at the end of the response, which `response_without_public_key` leaves out.
+    pub fn response_public_key(&self) -> Vec<u8> {
+        self.response[self.public_key_position()..].to_vec()
+    }
+
+    /// The hash of the response.
+    #[wasm_bindgen(getter)]
+    pub fn contribution_hash(&self) -> Vec<u8> {
+        self.contribution_hash.clone()
+    }
+
+    /// The serialized public key of the contribution, which is also written at the end of the response.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    /// The seed a beacon contribution's randomness was derived from, if this is one.
+    #[wasm_bindgen(getter)]
+    pub fn beacon_seed(&self) -> Option<Vec<u8>> {
+        self.beacon_seed.clone()
+    }
+
+    /// The Blake2b hash of the system entropy the contribution's randomness was
+    /// derived from, if it was, so the contributor can later show that fresh
+    /// randomness was used without revealing it.
+    #[wasm_bindgen(getter)]
+    pub fn randomness_commitment(&self) -> Option<Vec<u8>> {
+        self.randomness_commitment.clone()
+    }
+
+    /// How long each phase of the contribution took, if timing was requested.
+    #[wasm_bindgen(getter)]
+    pub fn timing(&self) -> Option<ContributionTiming> {
+        self.timing
+    }
+
+    /// The hashes of the chunks of the response, as `chunk_hashes` returns
+    /// them, as an array of `Uint8Array`s.
+    #[wasm_bindgen(js_name = chunk_hashes)]
+    pub fn chunk_hashes_array(&self, chunk_len: usize) -> Result<js_sys::Array, Phase1Error> {
+        Ok(self
+            .chunk_hashes(chunk_len)?
+            .iter()
+            .map(|hash| JsValue::from(js_sys::Uint8Array::from(hash.as_slice())))
+            .collect())
+    }
+
+    /// The indices of the chunks to upload again, as `verify_uploaded` returns
+    /// them, for an array of the uploaded chunks as `Uint8Array`s.
+    #[wasm_bindgen(js_name = verify_uploaded)]
+    pub fn verify_uploaded_array(
+        &self,
+        chunk_len: usize,
+        uploaded_chunks: js_sys::Array,
+    ) -> Result<js_sys::Array, Phase1Error> {
+        let uploaded_chunks: Vec<Vec<u8>> = uploaded_chunks
+            .iter()
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+            .collect();
+        Ok(self
+            .verify_uploaded(chunk_len, &uploaded_chunks)?
+            .into_iter()
+            .map(|index| JsValue::from(index as u32))
+            .collect())
+    }
 }
 
-#[cfg(not(test))]
+/// The zstd level `compressed_bytes` compresses with, which is zstd's default.
+#[cfg(feature = "zstd")]
+pub const TRANSPORT_COMPRESSION_LEVEL: i32 = 3;
+
+// Transport compression compresses the bytes of the response as a whole, for
+// uploading it. It is unrelated to the point compression of `UseCompression`,
+// which `compressed_bytes` leaves as it is.
+#[cfg(feature = "zstd")]
 #[wasm_bindgen]
-pub struct Phase1WASM {}
+impl ContributionResponse {
+    /// Compresses the response with zstd for uploading it, at `TRANSPORT_COMPRESSION_LEVEL`.
+    /// `decompress_response` reverses it.
+    pub fn compressed_bytes(&self) -> Result<Vec<u8>, Phase1Error> {
+        self.compressed_bytes_with_level(TRANSPORT_COMPRESSION_LEVEL)
+    }
 
-#[cfg(not(test))]
-impl Phase1WASM {
-    pub fn contribute_full(
-        curve_kind: &str,
-        proving_system: &str,
-        batch_size: usize,
-        power: usize,
-        challenge: &[u8],
-    ) -> Result<ContributionResponse, String> {
-        let rng = get_rng(&user_system_randomness());
-        let proving_system = proving_system_from_str(proving_system).expect("invalid proving system");
-        match curve_from_str(curve_kind).expect("invalid curve_kind") {
-            CurveKind::Bls12_377 => contribute_challenge(
-                &challenge,
-                &get_parameters_full::<Bls12_377>(proving_system, power, batch_size),
-                rng,
-            ),
-            CurveKind::BW6 => contribute_challenge(
-                &challenge,
-                &get_parameters_full::<BW6_761>(proving_system, power, batch_size),
-                rng,
-            ),
-        }
+    /// Compresses the response with zstd at `level`, from 1 to 22, trading
+    /// the time it takes for a smaller upload.
+    pub fn compressed_bytes_with_level(&self, level: i32) -> Result<Vec<u8>, Phase1Error> {
+        zstd::encode_all(self.response.as_slice(), level).map_err(|e| Phase1Error::TransportCompression(e.to_string()))
     }
+}
 
-    pub fn contribute_chunked(
-        curve_kind: &'static str,
-        proving_system: &str,
-        batch_size: usize,
-        power: usize,
-        chunk_index: usize,
-        chunk_size: usize,
-        seed: &[u8],
-        challenge: Vec<u8>,
-        worker: &crate::pool::WorkerProcess,
-        thread_pool_size: usize,
-    ) -> Result<ContributionResponse, String> {
-        // Configure a rayon thread pool which will pull web workers from `pool`.
-        let thread_pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(thread_pool_size)
-            .spawn_handler(|thread| Ok(worker.run(|| thread.run()).unwrap()))
-            .build()
-            .unwrap();
-
-        let rng = derive_rng_from_seed(seed);
-        let proving_system = proving_system_from_str(proving_system).expect("invalid proving system");
-
-        let (tx, rx) = oneshot::channel();
-        thread_pool.install(|| {
-            let res = match curve_from_str(curve_kind).expect("invalid curve_kind") {
-                CurveKind::Bls12_377 => contribute_challenge(
-                    &challenge,
-                    &get_parameters_chunked::<Bls12_377>(proving_system, power, batch_size, chunk_index, chunk_size),
-                    rng,
-                ),
-                CurveKind::BW6 => contribute_challenge(
-                    &challenge,
-                    &get_parameters_chunked::<BW6_761>(proving_system, power, batch_size, chunk_index, chunk_size),
-                    rng,
-                ),
-            };
-            drop(tx.send(res));
-        });
+/// Decompresses a response compressed for transport by `compressed_bytes`,
+/// returning the response as it was contributed. This doesn't decompress
+/// any points, which `next_challenge` does.
+#[cfg(feature = "zstd")]
+#[wasm_bindgen]
+pub fn decompress_response(bytes: &[u8]) -> Result<Vec<u8>, Phase1Error> {
+    zstd::decode_all(bytes).map_err(|e| Phase1Error::TransportCompression(e.to_string()))
+}
 
-        rx.recv().unwrap()
+// CBOR keeps the bytes of the response as byte strings, where JSON has them
+// as hex strings, so the encoding is around half as long.
+#[cfg(feature = "cbor")]
+#[wasm_bindgen]
+impl ContributionResponse {
+    /// Serializes the response to CBOR, a compact binary alternative to `to_json`.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, Phase1Error> {
+        let mut bytes = vec![];
+        ciborium::ser::into_writer(self, &mut bytes).map_err(|e| Phase1Error::Encoding(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Deserializes a response serialized by `to_cbor`, rejecting one of a
+    /// format this version can't read with `Phase1Error::UnsupportedFormat`.
+    pub fn from_cbor(bytes: &[u8]) -> Result<ContributionResponse, Phase1Error> {
+        let response: ContributionResponse =
+            ciborium::de::from_reader(bytes).map_err(|e| Phase1Error::Encoding(e.to_string()))?;
+        response.check_format()?;
+        Ok(response)
     }
 }
 
-pub fn get_parameters_full<E: PairingEngine>(
-    proving_system: ProvingSystem,
-    power: usize,
-    batch_size: usize,
-) -> Phase1Parameters<E> {
-    Phase1Parameters::<E>::new_full(proving_system, power, batch_size)
+fn response_format_magic() -> String {
+    RESPONSE_FORMAT_MAGIC.to_string()
 }
 
-pub fn get_parameters_chunked<E: PairingEngine>(
-    proving_system: ProvingSystem,
-    power: usize,
-    batch_size: usize,
-    chunk_index: usize,
-    chunk_size: usize,
-) -> Phase1Parameters<E> {
-    Phase1Parameters::<E>::new_chunk(
-        ContributionMode::Chunked,
-        chunk_index,
-        chunk_size,
-        proving_system,
-        power,
-        batch_size,
-    )
+fn response_format_version() -> u16 {
+    RESPONSE_FORMAT_VERSION
 }
 
-pub fn contribute_challenge<E: PairingEngine + Sync>(
-    challenge: &[u8],
-    parameters: &Phase1Parameters<E>,
-    mut rng: impl Rng + CryptoRng,
-) -> Result<ContributionResponse, String> {
-    let expected_challenge_length = match COMPRESSED_INPUT {
-        UseCompression::Yes => parameters.contribution_size,
-        UseCompression::No => parameters.accumulator_size,
-    };
+impl ContributionResponse {
+    /// Checks that the response was recorded with `RESPONSE_FORMAT_MAGIC` and
+    /// `RESPONSE_FORMAT_VERSION`, as a response of another version may have a
+    /// layout this version reads wrongly.
+    pub fn check_format(&self) -> Result<(), Phase1Error> {
+        if self.format_magic != RESPONSE_FORMAT_MAGIC {
+            return Err(Phase1Error::UnsupportedFormat(format!(
+                "the marker should be {}, but it's {}",
+                RESPONSE_FORMAT_MAGIC, self.format_magic
+            )));
+        }
+        if self.format_version != RESPONSE_FORMAT_VERSION {
+            return Err(Phase1Error::UnsupportedFormat(format!(
+                "this version reads responses of format version {}, but it's {}",
+                RESPONSE_FORMAT_VERSION, self.format_version
+            )));
+        }
+        Ok(())
+    }
 
-    if challenge.len() != expected_challenge_length {
-        return Err(format!(
-            "The size of challenge file should be {}, but it's {}, so something isn't right.",
-            expected_challenge_length,
-            challenge.len()
-        ));
+    /// The position of the public key in the response, which is as long as
+    /// `public_key_size`. A response that isn't held is empty.
+    fn public_key_position(&self) -> usize {
+        self.response.len().saturating_sub(self.public_key.len())
     }
 
-    let required_output_length = match COMPRESSED_OUTPUT {
-        UseCompression::Yes => parameters.contribution_size,
-        UseCompression::No => parameters.accumulator_size + parameters.public_key_size,
+    /// The hashes of the consecutive `chunk_len` byte chunks of the response,
+    /// the last of which may be shorter, so an upload of the response can be
+    /// resumed by checking which chunks already arrived intact. These are
+    /// only for the transport of the response, and have nothing to do with
+    /// the chunks of a chunked ceremony. `chunk_len` must not be zero.
+    pub fn chunk_hashes(&self, chunk_len: usize) -> Result<Vec<Vec<u8>>, Phase1Error> {
+        if chunk_len == 0 {
+            return Err(Phase1Error::InvalidParameters(
+                "the length of the chunks of an upload should be positive".to_string(),
+            ));
+        }
+        Ok(self
+            .response
+            .chunks(chunk_len)
+            .map(|chunk| calculate_hash(chunk).to_vec())
+            .collect())
+    }
+
+    /// Compares the chunks of the response which were uploaded, in order, with
+    /// `chunk_hashes`, and returns the indices of those to upload again: the
+    /// uploaded chunks which differ from the response, and those missing.
+    pub fn verify_uploaded(&self, chunk_len: usize, uploaded_chunks: &[Vec<u8>]) -> Result<Vec<usize>, Phase1Error> {
+        Ok(self
+            .chunk_hashes(chunk_len)?
+            .iter()
+            .enumerate()
+            .filter(|(index, hash)| match uploaded_chunks.get(*index) {
+                Some(uploaded) => !contribution_core::hashes_eq(calculate_hash(uploaded).as_slice(), hash),
+                None => true,
+            })
+            .map(|(index, _)| index)
+            .collect())
+    }
+
+    /// Returns the result of a contribution whose response was written
+    /// somewhere other than memory, so `response` is left empty.
+    #[cfg(all(not(target_arch = "wasm32"), not(feature = "verify-only")))]
+    pub(crate) fn from_contribution(contribution: contribution_core::Contribution) -> Self {
+        ContributionResponse {
+            format_magic: response_format_magic(),
+            format_version: RESPONSE_FORMAT_VERSION,
+            current_accumulator_hash: contribution.current_accumulator_hash,
+            response: vec![],
+            contribution_hash: contribution.contribution_hash,
+            public_key: contribution.public_key,
+            beacon_seed: None,
+            randomness_commitment: None,
+            timing: None,
+        }
+    }
+}
+
+/// Serializes bytes as hex strings in human-readable formats, which are much
+/// smaller in JSON than arrays of integers, and as byte strings in binary
+/// formats such as CBOR.
+mod hex_bytes {
+    use serde::{
+        de::{Error, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serializer,
     };
+    use std::fmt;
 
-    let mut response: Vec<u8> = vec![];
-    let current_accumulator_hash = calculate_hash(&challenge);
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.serialize_str(&hex::encode(bytes)),
+            false => serializer.serialize_bytes(bytes),
+        }
+    }
 
-    for i in 0..required_output_length {
-        response.push(current_accumulator_hash[i % current_accumulator_hash.len()]);
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        match deserializer.is_human_readable() {
+            true => hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom),
+            false => deserializer.deserialize_byte_buf(BytesVisitor),
+        }
     }
 
-    // Construct our keypair using the RNG we created above
-    let (public_key, private_key): (phase1::PublicKey<E>, phase1::PrivateKey<E>) =
-        match Phase1::key_generation(&mut rng, current_accumulator_hash.as_ref()) {
-            Ok(pair) => pair,
-            Err(_) => return Err("could not generate keypair".to_string()),
-        };
+    /// Reads a byte string, or an array of bytes from a format without byte strings.
+    struct BytesVisitor;
 
-    // This computes a transformation and writes it
-    match Phase1::computation(
-        &challenge,
-        &mut response,
-        COMPRESSED_INPUT,
-        COMPRESSED_OUTPUT,
-        CHECK_INPUT_CORRECTNESS,
-        &private_key,
-        &parameters,
-    ) {
-        Ok(_) => match public_key.write(&mut response, COMPRESSED_OUTPUT, &parameters) {
-            Ok(_) => {
-                let contribution_hash = calculate_hash(&response);
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
 
-                return Ok(ContributionResponse {
-                    current_accumulator_hash: current_accumulator_hash.as_slice().iter().cloned().collect(),
-                    response,
-                    contribution_hash: contribution_hash.as_slice().iter().cloned().collect(),
-                });
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a byte string")
+        }
+
+        fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(bytes.to_vec())
+        }
+
+        fn visit_byte_buf<E: Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(bytes)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
             }
-            Err(e) => {
-                return Err(e.to_string());
+            Ok(bytes)
+        }
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        /// The bytes serialized with `hex_bytes`, for serializing them inside an `Option`.
+        #[derive(Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct Bytes(#[serde(with = "super")] Vec<u8>);
+
+        pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => serializer.serialize_some(&Bytes(bytes.clone())),
+                None => serializer.serialize_none(),
             }
-        },
-        Err(_) => {
-            return Err("must contribute with the key".to_string());
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+            Ok(Option::<Bytes>::deserialize(deserializer)?.map(|Bytes(bytes)| bytes))
+        }
+    }
+}
+
+/// The time in milliseconds each phase of a contribution took.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct ContributionTiming {
+    pub key_generation_ms: f64,
+    pub computation_ms: f64,
+    pub write_ms: f64,
+    /// Hashing both the challenge and the response.
+    pub hash_ms: f64,
+}
+
+// Timings are compared bit for bit, so a timing always equals itself, even
+// with a NaN, which lets `ContributionResponse` be `Eq`.
+impl PartialEq for ContributionTiming {
+    fn eq(&self, other: &Self) -> bool {
+        let bits = |timing: &Self| {
+            [
+                timing.key_generation_ms.to_bits(),
+                timing.computation_ms.to_bits(),
+                timing.write_ms.to_bits(),
+                timing.hash_ms.to_bits(),
+            ]
+        };
+        bits(self) == bits(other)
+    }
+}
+
+impl Eq for ContributionTiming {}
+
+/// The outcome of each check of a verification, from `verify_detailed`, so a
+/// failed contribution shows whether the wrong file was sent or the
+/// contribution is invalid.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Both the challenge and the response have the expected lengths.
+    pub length_ok: bool,
+    /// Every point of the response, including those of the public key, is in its prime-order subgroup.
+    pub subgroup_ok: bool,
+    /// The proofs of knowledge of the public key hold, and the response is the
+    /// challenge transformed with its secrets.
+    pub ratio_check_ok: bool,
+    /// The response records the hash of the challenge.
+    pub hash_link_ok: bool,
+}
+
+#[wasm_bindgen]
+impl VerificationReport {
+    /// Whether every check passed, which `verify_response` would accept.
+    #[wasm_bindgen(getter)]
+    pub fn is_valid(&self) -> bool {
+        self.length_ok && self.subgroup_ok && self.ratio_check_ok && self.hash_link_ok
+    }
+}
+
+/// The components of a response, as `parse_response` reads them without
+/// verifying the contribution. The elements of the public key are serialized
+/// as they are in the response, compressed unless it was written uncompressed.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedResponse {
+    challenge_hash: Vec<u8>,
+    body_length: usize,
+    compressed: bool,
+    public_key_compressed: bool,
+    tau_powers_g1_length: usize,
+    tau_powers_length: usize,
+    public_key: Vec<u8>,
+    g1_size: usize,
+    g2_size: usize,
+}
+
+#[wasm_bindgen]
+impl ParsedResponse {
+    /// The hash of the challenge the response records it was contributed to.
+    #[wasm_bindgen(getter)]
+    pub fn challenge_hash(&self) -> Vec<u8> {
+        self.challenge_hash.clone()
+    }
+
+    /// The length of the accumulator between the challenge hash and the public key.
+    #[wasm_bindgen(getter)]
+    pub fn body_length(&self) -> usize {
+        self.body_length
+    }
+
+    /// Whether the points of the accumulator are compressed.
+    #[wasm_bindgen(getter)]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// Whether the points of the public key are compressed.
+    #[wasm_bindgen(getter)]
+    pub fn is_public_key_compressed(&self) -> bool {
+        self.public_key_compressed
+    }
+
+    /// The number of tau powers in G1 the accumulator holds.
+    #[wasm_bindgen(getter)]
+    pub fn tau_powers_g1_length(&self) -> usize {
+        self.tau_powers_g1_length
+    }
+
+    /// The number of tau powers in G2, and of alpha and beta powers in G1, the accumulator holds.
+    #[wasm_bindgen(getter)]
+    pub fn tau_powers_length(&self) -> usize {
+        self.tau_powers_length
+    }
+
+    /// The serialized public key, at the end of the response.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    /// The pair of G1 points `(s, s * tau)` of the public key.
+    pub fn public_key_tau_g1(&self) -> Vec<u8> {
+        self.public_key_elements(0, 2 * self.g1_size)
+    }
+
+    /// The pair of G1 points `(s, s * alpha)` of the public key.
+    pub fn public_key_alpha_g1(&self) -> Vec<u8> {
+        self.public_key_elements(2 * self.g1_size, 2 * self.g1_size)
+    }
+
+    /// The pair of G1 points `(s, s * beta)` of the public key.
+    pub fn public_key_beta_g1(&self) -> Vec<u8> {
+        self.public_key_elements(4 * self.g1_size, 2 * self.g1_size)
+    }
+
+    pub fn public_key_tau_g2(&self) -> Vec<u8> {
+        self.public_key_elements(6 * self.g1_size, self.g2_size)
+    }
+
+    pub fn public_key_alpha_g2(&self) -> Vec<u8> {
+        self.public_key_elements(6 * self.g1_size + self.g2_size, self.g2_size)
+    }
+
+    pub fn public_key_beta_g2(&self) -> Vec<u8> {
+        self.public_key_elements(6 * self.g1_size + 2 * self.g2_size, self.g2_size)
+    }
+}
+
+impl ParsedResponse {
+    /// The `length` bytes of the public key from `start`, which follow the
+    /// order its fields are serialized in.
+    fn public_key_elements(&self, start: usize, length: usize) -> Vec<u8> {
+        self.public_key[start..start + length].to_vec()
+    }
+}
+
+/// Where two responses to the same challenge diverge, from `diff_responses`,
+/// for debugging verifications which disagree. It compares bytes, and says
+/// nothing about whether either response is a valid contribution.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResponseDiff {
+    first_difference: Option<usize>,
+    a_length: usize,
+    b_length: usize,
+    bodies_match: bool,
+    public_keys_match: bool,
+}
+
+#[wasm_bindgen]
+impl ResponseDiff {
+    /// The offset of the first byte which differs, or the length of the
+    /// shorter response if it's the start of the longer one. `None` when the
+    /// responses are the same.
+    #[wasm_bindgen(getter)]
+    pub fn first_difference(&self) -> Option<usize> {
+        self.first_difference
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn a_length(&self) -> usize {
+        self.a_length
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn b_length(&self) -> usize {
+        self.b_length
+    }
+
+    /// Whether the responses match up to their public keys.
+    #[wasm_bindgen(getter)]
+    pub fn bodies_match(&self) -> bool {
+        self.bodies_match
+    }
+
+    /// Whether the public keys at the ends of the responses match.
+    #[wasm_bindgen(getter)]
+    pub fn public_keys_match(&self) -> bool {
+        self.public_keys_match
+    }
+}
+
+/// Measures elapsed time with `Performance::now` on WASM and `Instant` natively.
+#[cfg(not(feature = "verify-only"))]
+struct Stopwatch {
+    #[cfg(target_arch = "wasm32")]
+    start: f64,
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl Stopwatch {
+    #[cfg(target_arch = "wasm32")]
+    fn start() -> Self {
+        Stopwatch {
+            start: performance_now(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start() -> Self {
+        Stopwatch {
+            start: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn elapsed_ms(&self) -> f64 {
+        performance_now() - self.start
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn elapsed_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+/// Reads `performance.now()` from the global scope, which is a window or a web
+/// worker, or returns zero where it isn't available.
+#[cfg(all(target_arch = "wasm32", not(feature = "verify-only")))]
+fn performance_now() -> f64 {
+    use wasm_bindgen::JsCast;
+
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))
+        .ok()
+        .and_then(|performance| performance.dyn_into::<web_sys::Performance>().ok())
+        .map(|performance| performance.now())
+        .unwrap_or_default()
+}
+
+/// How the challenge and response are serialized, and how thoroughly the
+/// challenge is checked when it is read. The defaults are an uncompressed
+/// challenge, a compressed response, and no input checks.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct SerializationOptions {
+    #[wasm_bindgen(skip)]
+    pub compressed_input: UseCompression,
+    #[wasm_bindgen(skip)]
+    pub compressed_output: UseCompression,
+    #[wasm_bindgen(skip)]
+    pub check_input: CheckForCorrectness,
+}
+
+#[wasm_bindgen]
+impl SerializationOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new(compressed_input: bool, compressed_output: bool, check_input: bool) -> SerializationOptions {
+        let compression = |compressed| match compressed {
+            true => UseCompression::Yes,
+            false => UseCompression::No,
+        };
+        SerializationOptions {
+            compressed_input: compression(compressed_input),
+            compressed_output: compression(compressed_output),
+            check_input: match check_input {
+                true => CheckForCorrectness::Full,
+                false => CheckForCorrectness::No,
+            },
+        }
+    }
+
+    /// A compressed challenge and a compressed response, with no input checks.
+    pub fn compressed_challenge() -> SerializationOptions {
+        SerializationOptions {
+            compressed_input: UseCompression::Yes,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SerializationOptions {
+    fn default() -> Self {
+        SerializationOptions {
+            compressed_input: COMPRESSED_INPUT,
+            compressed_output: COMPRESSED_OUTPUT,
+            check_input: CHECK_INPUT_CORRECTNESS,
+        }
+    }
+}
+
+/// Optional behaviour for a contribution. The default options contribute
+/// exactly as `contribute_challenge` does.
+#[cfg(not(feature = "verify-only"))]
+pub struct ContributionOptions<'a> {
+    pub serialization: SerializationOptions,
+    /// Reads the public key back from the response and checks it matches the
+    /// one generated, which can be disabled for speed. Enabled by default.
+    pub strict: bool,
+    /// Called with the fraction of the contribution completed so far, from 0.0 to 1.0.
+    /// Passing `None` preserves the behaviour without progress reporting exactly.
+    pub progress: Option<&'a mut dyn FnMut(f64)>,
+    /// Polled throughout the contribution, which returns `Phase1Error::Cancelled`
+    /// as soon as the token is cancelled.
+    pub cancel: Option<&'a CancelToken>,
+    /// Records how long each phase takes into `ContributionResponse::timing`.
+    pub timing: bool,
+    /// The hash of the challenge with `hash_algorithm`, if the caller already
+    /// computed it, for example while downloading the challenge. It is trusted
+    /// as is, and a wrong hash makes the response fail verification.
+    pub current_accumulator_hash: Option<&'a [u8]>,
+    /// Called with a checkpoint once the keypair is generated, and again once
+    /// the challenge is transformed, from which the contribution can be resumed.
+    /// The checkpoint is handed over rather than lent, as it can't be cloned.
+    pub checkpoint: Option<&'a mut dyn FnMut(Checkpoint)>,
+    /// Resumes the contribution from a checkpoint of it, instead of generating
+    /// a new keypair. The RNG is then unused.
+    pub resume: Option<&'a Checkpoint>,
+    /// What the response is filled with before the computation overwrites it,
+    /// which never changes the response, but can make buffers easier to compare
+    /// while debugging.
+    pub fill: ResponseFill,
+    /// The hash of the challenge and of the response. A response contributed
+    /// with another hash than Blake2b only verifies with `verify_response_with_hash`.
+    pub hash_algorithm: HashAlgorithm,
+    /// How many times the keypair is generated, each time with fresh randomness
+    /// from the RNG, before `Phase1Error::KeyGeneration` is returned.
+    /// `contribution_core::KEY_GENERATION_ATTEMPTS` by default.
+    pub key_generation_attempts: u32,
+    /// How the public key at the end of the response is serialized, separately
+    /// from the accumulator, for verifiers built against the older format with
+    /// an uncompressed public key. Compressed by default. A response with an
+    /// uncompressed public key is `contribution_core::response_len_with_public_key` long,
+    /// and only those verifiers verify it.
+    pub public_key_compression: UseCompression,
+    /// Whether to `reserve_memory` for the contribution up front, on WASM, by
+    /// its `estimate_contribution_memory`. On by default.
+    pub reserve_memory: bool,
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl<'a> Default for ContributionOptions<'a> {
+    fn default() -> Self {
+        ContributionOptions {
+            serialization: SerializationOptions::default(),
+            strict: true,
+            progress: None,
+            cancel: None,
+            timing: false,
+            current_accumulator_hash: None,
+            checkpoint: None,
+            resume: None,
+            fill: ResponseFill::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            key_generation_attempts: contribution_core::KEY_GENERATION_ATTEMPTS,
+            public_key_compression: UseCompression::Yes,
+            reserve_memory: true,
         }
     }
 }
+
+/// A flag which JS can set to abort a contribution in progress.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+#[wasm_bindgen]
+impl CancelToken {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// Returns a token sharing this token's flag, to be passed into a
+    /// contribution while this one is kept around to cancel it.
+    pub fn handle(&self) -> CancelToken {
+        self.clone()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// The number of steps a single unit of work reported to a `ProgressReporter` is divided into.
+#[cfg(not(feature = "verify-only"))]
+pub const PROGRESS_UNIT_STEPS: u32 = 10_000;
+
+/// The progress of contributions running on the worker pool, which the web
+/// workers add to and JS polls, for example from `requestAnimationFrame`.
+///
+/// The web workers can't call back into JS, so the completed steps are kept
+/// in an atomic counter in WASM memory instead. When the memory is shared,
+/// the main thread can read it while the calling worker is blocked in the
+/// contribution, with `Atomics.load` on a `Uint32Array` over the memory at
+/// `completed_ptr() / 4`, out of `total_steps()`.
+///
+/// Like a `CancelToken`, the counter is behind an `Arc`, so the reporter
+/// passed into a contribution can be a `handle()` of the one polled.
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ProgressReporter {
+    completed: Arc<AtomicU32>,
+    total_units: u32,
+}
+
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+impl ProgressReporter {
+    /// Creates a reporter for `total_units` units of work, one per chunk
+    /// contributed to.
+    #[wasm_bindgen(constructor)]
+    pub fn new(total_units: u32) -> ProgressReporter {
+        ProgressReporter {
+            completed: Arc::new(AtomicU32::new(0)),
+            total_units,
+        }
+    }
+
+    /// Returns a reporter sharing this reporter's counter, to be passed into a
+    /// contribution while this one is kept around to poll it.
+    pub fn handle(&self) -> ProgressReporter {
+        self.clone()
+    }
+
+    /// The fraction of all the units of work completed so far, from 0.0 to 1.0.
+    #[wasm_bindgen(getter)]
+    pub fn fraction(&self) -> f64 {
+        if self.total_units == 0 {
+            return 1.0;
+        }
+        (self.completed.load(Ordering::SeqCst) as f64 / self.total_steps() as f64).min(1.0)
+    }
+
+    /// The address of the counter of completed steps in WASM memory.
+    pub fn completed_ptr(&self) -> usize {
+        self.completed.as_ref() as *const AtomicU32 as usize
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_steps(&self) -> u32 {
+        self.total_units.saturating_mul(PROGRESS_UNIT_STEPS)
+    }
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl ProgressReporter {
+    /// Returns a progress callback for one unit of work, which adds the
+    /// progress of the unit since its last call to the counter.
+    pub(crate) fn unit(&self) -> impl FnMut(f64) + '_ {
+        let mut reported = 0;
+        move |fraction| {
+            let steps = (fraction.clamp(0.0, 1.0) * PROGRESS_UNIT_STEPS as f64) as u32;
+            if steps > reported {
+                self.completed.fetch_add(steps - reported, Ordering::SeqCst);
+                reported = steps;
+            }
+        }
+    }
+}
+
+/// The sizes in bytes of the buffers involved in a contribution.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParameterSizes {
+    accumulator_size: usize,
+    contribution_size: usize,
+    public_key_size: usize,
+}
+
+#[wasm_bindgen]
+impl ParameterSizes {
+    /// The size of an uncompressed challenge.
+    #[wasm_bindgen(getter)]
+    pub fn accumulator_size(&self) -> usize {
+        self.accumulator_size
+    }
+
+    /// The size of a compressed response, including the public key.
+    #[wasm_bindgen(getter)]
+    pub fn contribution_size(&self) -> usize {
+        self.contribution_size
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn public_key_size(&self) -> usize {
+        self.public_key_size
+    }
+}
+
+impl ParameterSizes {
+    /// The size of a response, including the public key.
+    pub(crate) fn response_size(&self, compressed_output: UseCompression) -> usize {
+        match compressed_output {
+            UseCompression::Yes => self.contribution_size,
+            UseCompression::No => self.accumulator_size + self.public_key_size,
+        }
+    }
+}
+
+impl<E: PairingEngine> From<&Phase1Parameters<E>> for ParameterSizes {
+    fn from(parameters: &Phase1Parameters<E>) -> Self {
+        ParameterSizes {
+            accumulator_size: parameters.accumulator_size,
+            contribution_size: parameters.contribution_size,
+            public_key_size: parameters.public_key_size,
+        }
+    }
+}
+
+/// The number of powers of each kind in an accumulator, which for a chunk are
+/// only those the chunk holds.
+///
+/// In a full ceremony of Groth16, there are `2^(power + 1) - 1` tau powers in
+/// G1, and `2^power` of each other kind. In one of Marlin, there are `2^power`
+/// tau powers in G1, the `power + 2` tau powers in G2 of the degree bounds,
+/// `3 + 3 * power` alpha powers, and no beta powers. The extra Marlin powers
+/// are all in the first chunk.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerCounts {
+    tau_powers_g1: usize,
+    tau_powers_g2: usize,
+    alpha_tau: usize,
+    beta_tau: usize,
+}
+
+#[wasm_bindgen]
+impl PowerCounts {
+    #[wasm_bindgen(getter)]
+    pub fn tau_powers_g1(&self) -> usize {
+        self.tau_powers_g1
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn tau_powers_g2(&self) -> usize {
+        self.tau_powers_g2
+    }
+
+    /// The number of alpha powers in G1.
+    #[wasm_bindgen(getter)]
+    pub fn alpha_tau(&self) -> usize {
+        self.alpha_tau
+    }
+
+    /// The number of beta powers in G1.
+    #[wasm_bindgen(getter)]
+    pub fn beta_tau(&self) -> usize {
+        self.beta_tau
+    }
+}
+
+impl<E: PairingEngine> From<&Phase1Parameters<E>> for PowerCounts {
+    fn from(parameters: &Phase1Parameters<E>) -> Self {
+        match parameters.proving_system {
+            ProvingSystem::Groth16 => PowerCounts {
+                tau_powers_g1: parameters.g1_chunk_size,
+                tau_powers_g2: parameters.other_chunk_size,
+                alpha_tau: parameters.other_chunk_size,
+                beta_tau: parameters.other_chunk_size,
+            },
+            // The extra Marlin elements are indexed by the power, as in `Phase1Parameters::new`.
+            ProvingSystem::Marlin if parameters.chunk_index == 0 => PowerCounts {
+                tau_powers_g1: parameters.g1_chunk_size,
+                tau_powers_g2: parameters.total_size_in_log2 + 2,
+                alpha_tau: 3 + 3 * parameters.total_size_in_log2,
+                beta_tau: 0,
+            },
+            ProvingSystem::Marlin => PowerCounts {
+                tau_powers_g1: parameters.g1_chunk_size,
+                tau_powers_g2: 0,
+                alpha_tau: 0,
+                beta_tau: 0,
+            },
+        }
+    }
+}
+
+/// The group of the tau powers `ResponseSelector::select` extracts.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseGroup {
+    G1,
+    G2,
+}
+
+/// Extracts the tau powers in one group from the body of a response, for a
+/// downstream system which only consumes those, without shipping the whole
+/// accumulator. The alpha and beta powers are left out of either group.
+///
+/// The offsets are computed from the `Phase1Parameters` of the response, and
+/// the powers are extracted as serialized, so compressed in a compressed response.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResponseSelector {
+    response_length: usize,
+    tau_powers_g1: std::ops::Range<usize>,
+    tau_powers_g2: std::ops::Range<usize>,
+}
+
+#[wasm_bindgen]
+impl ResponseSelector {
+    /// Returns the serialized tau powers in `group` of `response`, in order,
+    /// as a standalone buffer.
+    pub fn select(&self, response: &[u8], group: ResponseGroup) -> Result<Vec<u8>, Phase1Error> {
+        if response.len() != self.response_length {
+            return Err(Phase1Error::ResponseLength {
+                expected: self.response_length,
+                actual: response.len(),
+            });
+        }
+        let range = match group {
+            ResponseGroup::G1 => self.tau_powers_g1.clone(),
+            ResponseGroup::G2 => self.tau_powers_g2.clone(),
+        };
+        Ok(response[range].to_vec())
+    }
+}
+
+impl ResponseSelector {
+    /// The selector of the responses with `parameters`, serialized with `compressed_output`.
+    /// The tau powers follow the hash at the start of the response, first
+    /// those in G1, then those in G2.
+    pub fn new<E: PairingEngine>(parameters: &Phase1Parameters<E>, compressed_output: UseCompression) -> Self {
+        let (g1_size, g2_size) = match compressed_output {
+            UseCompression::Yes => (parameters.curve.g1_compressed_size, parameters.curve.g2_compressed_size),
+            UseCompression::No => (parameters.curve.g1_size, parameters.curve.g2_size),
+        };
+        let counts = PowerCounts::from(parameters);
+
+        let tau_powers_g1_end = parameters.hash_size + counts.tau_powers_g1 * g1_size;
+        ResponseSelector {
+            response_length: contribution_core::response_len(parameters, compressed_output),
+            tau_powers_g1: parameters.hash_size..tau_powers_g1_end,
+            tau_powers_g2: tau_powers_g1_end..tau_powers_g1_end + counts.tau_powers_g2 * g2_size,
+        }
+    }
+}
+
+/// Initialize the following hooks:
+///
+/// + console error panic hook - to display panic messages in the console
+///
+/// The hooks are installed at most once, however many times this is called.
+/// Returns whether this call installed them.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn init_hooks() -> bool {
+    static INIT: std::sync::Once = std::sync::Once::new();
+
+    let mut installed = false;
+    INIT.call_once(|| {
+        std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+        installed = true;
+    });
+    installed
+}
+
+/// The curve names `curve_from_str` accepts.
+pub const CURVES: &[&str] = &["bls12_377", "bw6"];
+
+/// The proving system names `proving_system_from_str` accepts.
+pub const PROVING_SYSTEMS: &[&str] = &["groth16", "marlin"];
+
+/// Returns the array of curve names the contribution functions accept, so a
+/// UI can offer them and check input against them.
+#[wasm_bindgen]
+pub fn valid_curves() -> js_sys::Array {
+    CURVES.iter().map(|curve| JsValue::from_str(curve)).collect()
+}
+
+/// Returns the array of proving system names the contribution functions accept.
+#[wasm_bindgen]
+pub fn valid_proving_systems() -> js_sys::Array {
+    PROVING_SYSTEMS
+        .iter()
+        .map(|proving_system| JsValue::from_str(proving_system))
+        .collect()
+}
+
+/// The number of bytes of the Blake2b hash a parameters fingerprint keeps.
+const FINGERPRINT_LENGTH: usize = 16;
+
+/// Returns a short hex fingerprint of the ceremony parameters, and of the
+/// compression this build contributes with. A coordinator can publish the
+/// fingerprint it expects, so that a client which computes another one
+/// refuses to contribute.
+#[wasm_bindgen]
+pub fn parameters_fingerprint(
+    curve_kind: &str,
+    proving_system: &str,
+    batch_size: usize,
+    power: usize,
+) -> Result<String, Phase1Error> {
+    curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+    proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+
+    let compressed = |compression: UseCompression| matches!(compression, UseCompression::Yes);
+    let summary = format!(
+        concat!(
+            "phase1-wasm parameters v1;curve={};proving_system={};batch_size={};power={};",
+            "compressed_input={};compressed_output={}"
+        ),
+        curve_kind.to_lowercase(),
+        proving_system.to_lowercase(),
+        batch_size,
+        power,
+        compressed(COMPRESSED_INPUT),
+        compressed(COMPRESSED_OUTPUT),
+    );
+    Ok(hex::encode(&calculate_hash(summary.as_bytes())[..FINGERPRINT_LENGTH]))
+}
+
+#[wasm_bindgen]
+pub struct Phase1WASM {}
+
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+impl Phase1WASM {
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_full(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: Vec<u8>,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let randomness = system_randomness()?;
+        // Only the hash of the entropy leaves this function, never the entropy itself.
+        let randomness_commitment = calculate_hash(&randomness).to_vec();
+        let rng = get_rng(&randomness);
+
+        let mut report = |fraction: f64| report_progress(&progress, fraction);
+        let options = ContributionOptions {
+            serialization: serialization.unwrap_or_default(),
+            progress: Some(&mut report),
+            cancel: cancel.as_ref(),
+            ..Default::default()
+        };
+
+        let result = Self::contribute_full_with_options(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            &challenge,
+            rng,
+            options,
+        )?;
+
+        Ok(ContributionResponse {
+            randomness_commitment: Some(randomness_commitment),
+            ..result
+        })
+    }
+
+    /// Contributes like `contribute_full` to a compressed challenge, which
+    /// coordinators distribute to save bandwidth, writing a compressed response.
+    /// The challenge is checked to be `contribution_size` long without the public key.
+    ///
+    /// Each point of the challenge is decompressed as it is read, which takes a
+    /// square root in the base field. That is cheap next to raising the point
+    /// to its power, but still adds around a tenth to the computation, in
+    /// exchange for downloading half as much.
+    pub fn contribute_from_compressed(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: Vec<u8>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        Self::contribute_full(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            challenge,
+            Some(SerializationOptions::compressed_challenge()),
+            progress,
+            cancel,
+        )
+    }
+
+    /// Contributes with an RNG derived from `seed`, so the same seed and challenge
+    /// always produce the same response.
+    ///
+    /// This is for testing and benchmarking only. It must not be used in a real
+    /// ceremony, since anyone who knows the seed can recover the toxic waste.
+    ///
+    /// The seed must be at least `MIN_SEED_LENGTH` bytes long.
+    pub fn contribute_full_with_seed(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: Vec<u8>,
+        seed: &[u8],
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let rng = rng_from_seed(seed)?;
+        Self::contribute_full_with_rng(curve_kind, proving_system, batch_size, power, &challenge, rng)
+    }
+
+    /// Makes a beacon contribution, whose randomness is derived from the public
+    /// `beacon_hash` by `beacon_seed` so that anyone can reproduce it. The
+    /// derived seed is returned with the response for auditing.
+    pub fn contribute_beacon(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: Vec<u8>,
+        beacon_hash: &[u8],
+        iterations: u32,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let seed = beacon_seed(beacon_hash, iterations);
+        let rng = rng_from_seed(&seed)?;
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let result = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            contribute_challenge(
+                &challenge,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                rng,
+            )
+        })?;
+
+        Ok(ContributionResponse {
+            beacon_seed: Some(seed),
+            ..result
+        })
+    }
+
+    /// Contributes like `contribute_full`, but calls `sink` with the response as
+    /// a sequence of `Uint8Array` slices instead of returning it.
+    pub fn contribute_full_streaming(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: Vec<u8>,
+        sink: js_sys::Function,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let rng = get_rng(&system_randomness()?);
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let sink = |slice: &[u8]| {
+            sink.call1(&JsValue::NULL, &js_sys::Uint8Array::from(slice))
+                .map(|_| ())
+                .map_err(|e| format!("{:?}", e))
+        };
+
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            contribute_challenge_streaming(
+                &challenge,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                rng,
+                sink,
+            )
+        })
+    }
+
+    /// Times a contribution of a single batch at the smallest power, in
+    /// milliseconds, so a UI can show an estimate before the contribution
+    /// starts. A batch of a larger power isn't slower per element, but the
+    /// batches of a real contribution are usually larger, so the timing of
+    /// its first batch gives the better estimate once it's known.
+    pub fn calibrate(curve_kind: &str, proving_system: &str) -> Result<f64, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            time_contribution(&get_parameters::<E>(
+                proving_system,
+                MIN_POWER,
+                MIN_SUGGESTED_BATCH_SIZE,
+                None,
+                None,
+            )?)
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl Phase1WASM {
+    /// Returns an approximate upper bound on the memory in bytes a full
+    /// contribution needs, so callers can check a `power` is feasible first.
+    /// See `estimate_contribution_memory` for the estimation model.
+    pub fn estimate_memory(
+        curve_kind: &str,
+        proving_system: &str,
+        power: usize,
+        batch_size: usize,
+    ) -> Result<usize, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let estimate = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            estimate_contribution_memory(&get_parameters::<E>(proving_system, power, batch_size, None, None)?)
+        });
+        Ok(estimate)
+    }
+
+    /// Returns the number of chunks a ceremony of `2^power` powers is split
+    /// into with chunks of `chunk_size` elements, whose indices are then
+    /// `0..num_chunks`. The last chunk holds fewer elements if `chunk_size`
+    /// doesn't divide the number of elements.
+    pub fn num_chunks(proving_system: &str, power: usize, chunk_size: usize) -> Result<usize, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        num_chunks(proving_system, power, chunk_size)
+    }
+
+    /// Returns the number of powers of each kind in the accumulator of a full
+    /// ceremony of `power`, which doesn't depend on the curve, so tooling can
+    /// show the scale of a ceremony and check it against a Phase 2.
+    pub fn power_counts(proving_system: &str, power: usize) -> Result<PowerCounts, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        // The batch size doesn't change the counts.
+        let batch_size = MIN_SUGGESTED_BATCH_SIZE;
+        let counts = dispatch_curve!(COMPILED_CURVE, |E| PowerCounts::from(&get_parameters::<E>(
+            proving_system,
+            power,
+            batch_size,
+            None,
+            None,
+        )?));
+        Ok(counts)
+    }
+
+    /// Returns the hash of `challenge`, which a contribution to it is linked to.
+    pub fn challenge_hash(challenge: &[u8]) -> Vec<u8> {
+        calculate_hash(challenge).as_slice().to_vec()
+    }
+
+    /// Returns the buffer sizes of a ceremony without contributing to it. The
+    /// sizes are those of a single chunk when both `chunk_index` and
+    /// `chunk_size` are given, and of the full ceremony otherwise.
+    pub fn parameter_sizes(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: Option<usize>,
+        chunk_size: Option<usize>,
+    ) -> Result<ParameterSizes, Phase1Error> {
+        let curve_kind = curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+
+        let sizes = dispatch_curve!(curve_kind, |E| ParameterSizes::from(&get_parameters::<E>(
+            proving_system,
+            power,
+            batch_size,
+            chunk_index,
+            chunk_size,
+        )?));
+        Ok(sizes)
+    }
+
+    /// Returns a `ResponseSelector` for the responses of a ceremony, written as
+    /// `serialization` says. The ceremony parameters are chosen as in `parameter_sizes`.
+    pub fn response_selector(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: Option<usize>,
+        chunk_size: Option<usize>,
+        serialization: Option<SerializationOptions>,
+    ) -> Result<ResponseSelector, Phase1Error> {
+        let curve_kind = curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let compressed_output = serialization.unwrap_or_default().compressed_output;
+
+        let selector = dispatch_curve!(curve_kind, |E| ResponseSelector::new(
+            &get_parameters::<E>(proving_system, power, batch_size, chunk_index, chunk_size)?,
+            compressed_output,
+        ));
+        Ok(selector)
+    }
+
+    /// Checks that `challenge` has the right length for the ceremony, and that
+    /// its elements are valid when `serialization` enables input checks,
+    /// without contributing to it. The ceremony parameters are chosen as in
+    /// `parameter_sizes`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn validate_challenge(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        chunk_index: Option<usize>,
+        chunk_size: Option<usize>,
+        serialization: Option<SerializationOptions>,
+    ) -> Result<(), Phase1Error> {
+        let curve_kind = curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let serialization = serialization.unwrap_or_default();
+
+        dispatch_curve!(curve_kind, |E| check_challenge(
+            challenge,
+            &get_parameters::<E>(proving_system, power, batch_size, chunk_index, chunk_size)?,
+            serialization,
+        ))
+    }
+
+    pub fn verify_contribution(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        response: &[u8],
+        serialization: Option<SerializationOptions>,
+    ) -> Result<bool, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let serialization = serialization.unwrap_or_default();
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_response_with_options(
+                &challenge,
+                &response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                serialization,
+            )
+        })
+    }
+
+    /// Verifies a single chunk's contribution and returns the hash of the
+    /// response, so it can be compared against the advertised one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_chunked(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        challenge: &[u8],
+        response: &[u8],
+        serialization: Option<SerializationOptions>,
+    ) -> Result<Vec<u8>, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let serialization = serialization.unwrap_or_default();
+
+        let num_chunks = num_chunks(proving_system, power, chunk_size)?;
+        if chunk_index >= num_chunks {
+            return Err(Phase1Error::ChunkIndexOutOfRange {
+                index: chunk_index,
+                num_chunks,
+            });
+        }
+
+        let is_valid = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_response_with_options(
+                &challenge,
+                &response,
+                &get_parameters::<E>(proving_system, power, batch_size, Some(chunk_index), Some(chunk_size))?,
+                serialization,
+            )
+        })?;
+
+        match is_valid {
+            true => Ok(calculate_hash(&response).as_slice().to_vec()),
+            false => Err(Phase1Error::InvalidContribution),
+        }
+    }
+
+    /// Verifies a full contribution, and returns the hash of the challenge the
+    /// next participant receives, so a chain of contributions can be verified
+    /// without deriving each challenge separately.
+    pub fn verify_and_next_hash(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        response: &[u8],
+    ) -> Result<Vec<u8>, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_response_and_next_hash(
+                challenge,
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+            )
+        })
+    }
+
+    /// Builds the challenge of the next participant from a full response, as
+    /// `next_challenge` does. The response should have been verified first,
+    /// since its points are decompressed without being checked.
+    pub fn next_challenge(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        response: &[u8],
+    ) -> Result<Vec<u8>, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            next_challenge(
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+            )
+        })
+    }
+
+    /// Returns the first challenge of a ceremony, as `initial_challenge` does,
+    /// so a coordinator can start a ceremony without any other tooling.
+    pub fn initial_challenge(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+    ) -> Result<Vec<u8>, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            initial_challenge(&get_parameters::<E>(proving_system, power, batch_size, None, None)?)
+        })
+    }
+
+    /// Verifies a whole ceremony of full contributions, as `verify_transcript`
+    /// does. `responses` is an array of `Uint8Array`s, in the order they were
+    /// contributed.
+    pub fn verify_transcript(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        initial_challenge: &[u8],
+        responses: js_sys::Array,
+    ) -> Result<bool, Phase1Error> {
+        let responses: Vec<Vec<u8>> = responses
+            .iter()
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+            .collect();
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_transcript(
+                initial_challenge,
+                &responses,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+            )
+        })
+    }
+
+    /// Reads the components of a full response, as `parse_response` does.
+    pub fn parse_response(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        response: &[u8],
+    ) -> Result<ParsedResponse, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            parse_response(
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+            )
+        })
+    }
+
+    /// Compares two full responses, as `diff_responses` does.
+    pub fn diff_responses(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        a: &[u8],
+        b: &[u8],
+    ) -> Result<ResponseDiff, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let diff = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            diff_responses(
+                a,
+                b,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+            )
+        });
+        Ok(diff)
+    }
+
+    /// Verifies a full contribution, reporting the outcome of each check.
+    pub fn verify_detailed(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        response: &[u8],
+    ) -> Result<VerificationReport, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let serialization = SerializationOptions::default();
+        let report = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_detailed(
+                challenge,
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                serialization,
+            )
+        });
+        Ok(report)
+    }
+
+    /// Suggests a batch size with which a contribution fits in the
+    /// `available_memory` bytes of the device, for example from
+    /// `navigator.deviceMemory`. See `suggest_batch_size_for` for the heuristic.
+    pub fn suggest_batch_size(
+        curve_kind: &str,
+        proving_system: &str,
+        power: usize,
+        available_memory: usize,
+    ) -> Result<usize, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        // The batch size doesn't change the sizes the suggestion depends on.
+        let batch_size = MIN_SUGGESTED_BATCH_SIZE;
+        let suggestion = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            suggest_batch_size_for(
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                available_memory,
+            )
+        });
+        Ok(suggestion)
+    }
+
+    /// Estimates how long a full contribution takes in milliseconds, from the
+    /// `measured_ms_per_batch` a batch of `batch_size` elements takes on this
+    /// device, such as the `computation_ms` of an earlier contribution
+    /// divided by its number of batches.
+    pub fn estimate_duration_ms(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        measured_ms_per_batch: f64,
+    ) -> Result<f64, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        if !(measured_ms_per_batch >= 0.0 && measured_ms_per_batch.is_finite()) {
+            return Err(Phase1Error::InvalidParameters(format!(
+                "the time per batch should be a nonnegative number of milliseconds, but it's {}",
+                measured_ms_per_batch
+            )));
+        }
+        let num_batches = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            num_batches(&get_parameters::<E>(proving_system, power, batch_size, None, None)?)
+        });
+        Ok(num_batches as f64 * measured_ms_per_batch)
+    }
+
+    /// Cheaply checks that the points of a full `response` are in the correct
+    /// prime-order subgroups, as a filter before a full verification. Returns
+    /// `false` rather than an error if any of them isn't.
+    pub fn quick_check(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        response: &[u8],
+    ) -> Result<bool, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            quick_check_response(
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                COMPRESSED_OUTPUT,
+            )
+        })
+    }
+
+    /// Returns whether a full `response` looks like it wasn't contributed at
+    /// all, as `looks_trivial` does, as a filter before a full verification.
+    pub fn looks_trivial(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        response: &[u8],
+    ) -> Result<bool, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            Ok(looks_trivial(
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                COMPRESSED_OUTPUT,
+            ))
+        })
+    }
+
+    /// Cheaply checks that a full `response` was contributed to `challenge`, by
+    /// the hash of the challenge it records, as a filter before a full verification.
+    pub fn check_links(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        response: &[u8],
+    ) -> Result<bool, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            check_response_links(
+                challenge,
+                response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                COMPRESSED_OUTPUT,
+            )
+        })
+    }
+
+    /// Combines the responses of every chunk of a ceremony, given as an array
+    /// of `Uint8Array`s in chunk order, into an uncompressed full accumulator.
+    pub fn combine_chunks(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_size: usize,
+        chunks: js_sys::Array,
+    ) -> Result<Vec<u8>, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let chunks: Vec<Vec<u8>> = chunks
+            .iter()
+            .map(|chunk| js_sys::Uint8Array::new(&chunk).to_vec())
+            .collect();
+
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            combine_responses(
+                &chunks,
+                &get_parameters::<E>(proving_system, power, batch_size, Some(0), Some(chunk_size))?,
+            )
+        })
+    }
+
+    /// Splits an uncompressed full challenge into the uncompressed challenges of
+    /// each chunk, returned as an array of `Uint8Array`s in chunk order.
+    pub fn split_into_chunks(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_size: usize,
+        full_challenge: &[u8],
+    ) -> Result<js_sys::Array, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let chunks = dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            split_challenge(
+                full_challenge,
+                &get_parameters::<E>(proving_system, power, batch_size, Some(0), Some(chunk_size))?,
+            )
+        })?;
+
+        Ok(chunks
+            .iter()
+            .map(|chunk| js_sys::Uint8Array::from(chunk.as_slice()))
+            .collect())
+    }
+}
+
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+impl Phase1WASM {
+    /// Contributes to a single chunk on this thread, calling `checkpoint` with
+    /// each `Checkpoint` taken along the way, so that an interrupted
+    /// contribution can be finished with `resume_from`. The RNG is derived
+    /// from `seed`, which must be at least `MIN_SEED_LENGTH` bytes long.
+    ///
+    /// The checkpoints contain the private key, and must be encrypted at rest.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunked_with_checkpoints(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        seed: &[u8],
+        challenge: Vec<u8>,
+        checkpoint: js_sys::Function,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let mut report = |taken: Checkpoint| drop(checkpoint.call1(&JsValue::NULL, &JsValue::from(taken)));
+        let options = ContributionOptions {
+            checkpoint: Some(&mut report),
+            ..Default::default()
+        };
+        contribute_chunk_with_options(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            &challenge,
+            rng_from_seed(seed)?,
+            options,
+        )
+    }
+
+    /// Finishes the contribution to a single chunk that `checkpoint` was taken from.
+    #[allow(clippy::too_many_arguments)]
+    pub fn resume_from(
+        checkpoint: &Checkpoint,
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        challenge: Vec<u8>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let options = ContributionOptions {
+            resume: Some(checkpoint),
+            ..Default::default()
+        };
+        // The keypair comes from the checkpoint, so the RNG is never used.
+        let rng = derive_rng_from_seed(&checkpoint.current_accumulator_hash());
+        contribute_chunk_with_options(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            &challenge,
+            rng,
+            options,
+        )
+    }
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl Phase1WASM {
+    /// Contributes to a full challenge with the caller's `rng`, as
+    /// `contribute_full` does with the system RNG.
+    pub fn contribute_full_with_rng(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        Self::contribute_full_with_options(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            challenge,
+            rng,
+            ContributionOptions::default(),
+        )
+    }
+
+    /// Verifies the prior contribution `prior_response` to `prior_challenge`,
+    /// then contributes to the challenge built from it, as `verify_then_contribute` does.
+    pub fn verify_then_contribute(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        prior_challenge: &[u8],
+        prior_response: &[u8],
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            verify_then_contribute(
+                prior_challenge,
+                prior_response,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                rng,
+            )
+        })
+    }
+
+    /// Contributes like `contribute_full_with_rng`, with the given `options`.
+    pub fn contribute_full_with_options(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        challenge: &[u8],
+        rng: impl Rng + CryptoRng,
+        options: ContributionOptions,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            contribute_challenge_with_options(
+                challenge,
+                &get_parameters::<E>(proving_system, power, batch_size, None, None)?,
+                rng,
+                options,
+            )
+        })
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "verify-only")))]
+#[wasm_bindgen]
+impl Phase1WASM {
+    /// Downloads the challenge at `url` and contributes to it like
+    /// `contribute_full`. The challenge is read into WASM memory as it
+    /// arrives, rather than downloaded into JS and then copied in, which
+    /// would briefly hold it twice.
+    ///
+    /// A response with another status than 200, or a `Content-Length` other
+    /// than the length of the challenge, is rejected before its body is read,
+    /// and a challenge of the wrong length is rejected before contributing.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn contribute_from_url(
+        curve_kind: String,
+        proving_system: String,
+        batch_size: usize,
+        power: usize,
+        url: String,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        use wasm_bindgen::JsCast;
+
+        let parsed_proving_system =
+            proving_system_from_str(&proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+        let compressed_input = serialization.unwrap_or_default().compressed_input;
+        let expected_length = dispatch_curve!(curve_from_str(&curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+            expected_challenge_len(
+                &get_parameters::<E>(parsed_proving_system, power, batch_size, None, None)?,
+                compressed_input,
+            )
+        });
+
+        let response = wasm_bindgen_futures::JsFuture::from(fetch_url(&url))
+            .await
+            .map_err(fetch_error)?;
+        let challenge = read_challenge(response.unchecked_into(), expected_length).await?;
+
+        Self::contribute_full(
+            &curve_kind,
+            &proving_system,
+            batch_size,
+            power,
+            challenge,
+            serialization,
+            progress,
+            cancel,
+        )
+    }
+}
+
+// The web worker pool is not available in tests.
+#[cfg(all(not(test), not(feature = "single_threaded"), not(feature = "verify-only")))]
+#[wasm_bindgen]
+impl Phase1WASM {
+    /// Contributes to a single chunk using the threads of `worker`. The RNG is
+    /// derived from `seed`, which must be at least `MIN_SEED_LENGTH` bytes long.
+    ///
+    /// This builds a new thread pool on every call, use a `Phase1Pool` to
+    /// contribute to several chunks.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunked(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        seed: &[u8],
+        challenge: Vec<u8>,
+        worker: &crate::pool::WorkerProcess,
+        thread_pool_size: usize,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        contribute_in_pool(
+            &build_thread_pool(worker, thread_pool_size)?,
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            seed,
+            challenge,
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )
+    }
+
+    /// Contributes to a single chunk like `contribute_chunked`, with the seed
+    /// `derive_chunk_seed` derives for the chunk from `master_seed`, which must
+    /// be at least `MIN_SEED_LENGTH` bytes long.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunked_with_master_seed(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        master_seed: &[u8],
+        challenge: Vec<u8>,
+        worker: &crate::pool::WorkerProcess,
+        thread_pool_size: usize,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        if master_seed.len() < MIN_SEED_LENGTH {
+            return Err(Phase1Error::SeedTooShort { len: master_seed.len() });
+        }
+        Self::contribute_chunked(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            &derive_chunk_seed(master_seed, chunk_index),
+            challenge,
+            worker,
+            thread_pool_size,
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )
+    }
+}
+
+#[cfg(all(not(test), feature = "single_threaded", not(feature = "verify-only")))]
+#[wasm_bindgen]
+impl Phase1WASM {
+    /// Contributes to a single chunk on the calling thread, for browsers
+    /// without `SharedArrayBuffer`, which can't run the web workers of a
+    /// thread pool. The RNG is derived from `seed`, which must be at least
+    /// `MIN_SEED_LENGTH` bytes long.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunked(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        seed: &[u8],
+        challenge: Vec<u8>,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        contribute_chunk_inline(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            seed,
+            challenge,
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )
+    }
+
+    /// Contributes to a single chunk like `contribute_chunked`, with the seed
+    /// `derive_chunk_seed` derives for the chunk from `master_seed`, which must
+    /// be at least `MIN_SEED_LENGTH` bytes long.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunked_with_master_seed(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        master_seed: &[u8],
+        challenge: Vec<u8>,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        if master_seed.len() < MIN_SEED_LENGTH {
+            return Err(Phase1Error::SeedTooShort { len: master_seed.len() });
+        }
+        Self::contribute_chunked(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            &derive_chunk_seed(master_seed, chunk_index),
+            challenge,
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )
+    }
+}
+
+/// The `Phase1Parameters` of a ceremony on any curve compiled in.
+pub(crate) enum CurveParameters {
+    #[cfg(feature = "curve-bls12_377")]
+    Bls12_377(Phase1Parameters<snarkvm_curves::bls12_377::Bls12_377>),
+    #[cfg(feature = "curve-bw6_761")]
+    BW6(Phase1Parameters<snarkvm_curves::bw6_761::BW6_761>),
+}
+
+/// An opaque handle on the `Phase1Parameters` of a ceremony, built once for
+/// callers making many contributions or verifications with the same
+/// parameters, which the `Phase1WASM` entry points rebuild on every call.
+#[wasm_bindgen]
+pub struct Phase1ParametersHandle {
+    parameters: CurveParameters,
+}
+
+#[wasm_bindgen]
+impl Phase1ParametersHandle {
+    /// Builds the parameters of a full ceremony, or of the chunk `chunk_index`
+    /// if both `chunk_index` and `chunk_size` are given, as the entry points do.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: Option<usize>,
+        chunk_size: Option<usize>,
+    ) -> Result<Phase1ParametersHandle, Phase1Error> {
+        let curve_kind = curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+        let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+
+        let parameters = match curve_kind {
+            #[cfg(feature = "curve-bls12_377")]
+            CurveKind::Bls12_377 => CurveParameters::Bls12_377(get_parameters(
+                proving_system,
+                power,
+                batch_size,
+                chunk_index,
+                chunk_size,
+            )?),
+            #[cfg(feature = "curve-bw6_761")]
+            CurveKind::BW6 => CurveParameters::BW6(get_parameters(
+                proving_system,
+                power,
+                batch_size,
+                chunk_index,
+                chunk_size,
+            )?),
+            #[allow(unreachable_patterns)]
+            curve_kind => return Err(Phase1Error::CurveNotCompiled(format!("{:?}", curve_kind))),
+        };
+        Ok(Phase1ParametersHandle { parameters })
+    }
+
+    /// The sizes of the accumulator and the contribution with these parameters.
+    pub fn sizes(&self) -> ParameterSizes {
+        dispatch_parameters!(&self.parameters, |parameters| ParameterSizes::from(parameters))
+    }
+
+    /// Verifies `response` to `challenge` as `Phase1WASM::verify_contribution` does.
+    pub fn verify(
+        &self,
+        challenge: &[u8],
+        response: &[u8],
+        serialization: Option<SerializationOptions>,
+    ) -> Result<bool, Phase1Error> {
+        let serialization = serialization.unwrap_or_default();
+        dispatch_parameters!(&self.parameters, |parameters| verify_response_with_options(
+            challenge,
+            response,
+            parameters,
+            serialization,
+        ))
+    }
+}
+
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+impl Phase1ParametersHandle {
+    /// Contributes to `challenge` with the system RNG, as `Phase1WASM::contribute_full` does.
+    pub fn contribute(&self, challenge: &[u8]) -> Result<ContributionResponse, Phase1Error> {
+        let randomness = system_randomness()?;
+        // Only the hash of the entropy leaves this function, never the entropy itself.
+        let randomness_commitment = calculate_hash(&randomness).to_vec();
+        let result = self.contribute_with_rng(challenge, get_rng(&randomness))?;
+
+        Ok(ContributionResponse {
+            randomness_commitment: Some(randomness_commitment),
+            ..result
+        })
+    }
+
+    /// Contributes with an RNG derived from `seed`, for testing and benchmarking
+    /// only, as `Phase1WASM::contribute_full_with_seed` does.
+    pub fn contribute_with_seed(&self, challenge: &[u8], seed: &[u8]) -> Result<ContributionResponse, Phase1Error> {
+        self.contribute_with_rng(challenge, rng_from_seed(seed)?)
+    }
+}
+
+#[cfg(not(feature = "verify-only"))]
+impl Phase1ParametersHandle {
+    /// Contributes to `challenge` with the caller's `rng`.
+    pub fn contribute_with_rng(
+        &self,
+        challenge: &[u8],
+        rng: impl Rng + CryptoRng,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        dispatch_parameters!(&self.parameters, |parameters| contribute_challenge(
+            challenge, parameters, rng
+        ))
+    }
+}
+
+/// A thread pool backed by web workers, which is built once and reused for
+/// every chunk contributed to.
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+#[wasm_bindgen]
+pub struct Phase1Pool {
+    thread_pool: rayon::ThreadPool,
+    // Keeps the web workers running the threads of the pool alive.
+    #[cfg(not(test))]
+    _worker: crate::pool::WorkerProcess,
+}
+
+// The web worker pool is not available in tests.
+#[cfg(all(not(test), not(feature = "single_threaded"), not(feature = "verify-only")))]
+#[wasm_bindgen]
+impl Phase1Pool {
+    /// Spawns `thread_pool_size` web workers and builds a thread pool on them.
+    #[wasm_bindgen(constructor)]
+    pub fn new(thread_pool_size: usize) -> Result<Phase1Pool, Phase1Error> {
+        check_thread_pool_size(thread_pool_size)?;
+        let worker = crate::pool::WorkerProcess::new(thread_pool_size)
+            .map_err(|e| Phase1Error::WorkerPool(format!("{:?}", e)))?;
+        Ok(Phase1Pool {
+            thread_pool: build_thread_pool(&worker, thread_pool_size)?,
+            _worker: worker,
+        })
+    }
+}
+
+#[cfg(all(
+    test,
+    not(target_arch = "wasm32"),
+    not(feature = "single_threaded"),
+    not(feature = "verify-only")
+))]
+impl Phase1Pool {
+    /// Builds a pool of native threads, for tests where web workers are not available.
+    pub(crate) fn new_native(thread_pool_size: usize) -> Result<Phase1Pool, Phase1Error> {
+        check_thread_pool_size(thread_pool_size)?;
+        Ok(Phase1Pool {
+            thread_pool: rayon::ThreadPoolBuilder::new()
+                .num_threads(thread_pool_size)
+                .build()
+                .map_err(|e| Phase1Error::WorkerPool(e.to_string()))?,
+        })
+    }
+}
+
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+#[wasm_bindgen]
+impl Phase1Pool {
+    /// Contributes to a single chunk using the threads of this pool. The RNG is
+    /// derived from `seed`, which must be at least `MIN_SEED_LENGTH` bytes long.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunk(
+        &self,
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        seed: &[u8],
+        challenge: Vec<u8>,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        contribute_in_pool(
+            &self.thread_pool,
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            seed,
+            challenge,
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )
+    }
+
+    /// Contributes like `contribute_chunk`, but reads the challenge from a
+    /// `Uint8Array` and writes the response into the caller's `response`
+    /// array, which must have exactly the length of the response. The
+    /// returned `ContributionResponse` then holds an empty response, saving
+    /// the copies of the response out of WASM memory.
+    ///
+    /// The challenge is copied into WASM memory before the contribution starts,
+    /// and the response is written only once it is complete. The caller must
+    /// not modify or read either array while the call is in progress.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunk_into(
+        &self,
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_index: usize,
+        chunk_size: usize,
+        seed: &[u8],
+        challenge: &js_sys::Uint8Array,
+        response: &js_sys::Uint8Array,
+        serialization: Option<SerializationOptions>,
+        progress: Option<js_sys::Function>,
+        cancel: Option<CancelToken>,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<ContributionResponse, Phase1Error> {
+        let sizes = Phase1WASM::parameter_sizes(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            Some(chunk_index),
+            Some(chunk_size),
+        )?;
+        let expected = sizes.response_size(serialization.unwrap_or_default().compressed_output);
+        if response.length() as usize != expected {
+            return Err(Phase1Error::ResponseLength {
+                expected,
+                actual: response.length() as usize,
+            });
+        }
+
+        let result = contribute_in_pool(
+            &self.thread_pool,
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            seed,
+            challenge.to_vec(),
+            serialization,
+            progress,
+            cancel,
+            reporter,
+        )?;
+        Ok(write_response(result, response))
+    }
+
+    /// Contributes to the adjacent chunks `start..end` one after another using
+    /// the threads of this pool. `seeds` and `challenges` are arrays of
+    /// `Uint8Array`s with one entry per chunk, in chunk order, and the
+    /// responses are returned as an array of `ContributionResponse`s in the
+    /// same order. A `reporter` for `end - start` units reaches 1.0 once
+    /// every chunk is contributed to.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contribute_chunk_range(
+        &self,
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_size: usize,
+        start: usize,
+        end: usize,
+        seeds: js_sys::Array,
+        challenges: js_sys::Array,
+        reporter: Option<ProgressReporter>,
+    ) -> Result<js_sys::Array, Phase1Error> {
+        let to_vecs = |array: js_sys::Array| -> Vec<Vec<u8>> {
+            array
+                .iter()
+                .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+                .collect()
+        };
+        let responses = self.contribute_chunks(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_size,
+            start..end,
+            &to_vecs(seeds),
+            to_vecs(challenges),
+            reporter.as_ref(),
+        )?;
+        Ok(responses.into_iter().map(JsValue::from).collect())
+    }
+}
+
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+impl Phase1Pool {
+    /// Contributes to each chunk of `chunks` in turn, with the seed and
+    /// challenge at the same position in `seeds` and `challenges`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn contribute_chunks(
+        &self,
+        curve_kind: &str,
+        proving_system: &str,
+        batch_size: usize,
+        power: usize,
+        chunk_size: usize,
+        chunks: std::ops::Range<usize>,
+        seeds: &[Vec<u8>],
+        challenges: Vec<Vec<u8>>,
+        reporter: Option<&ProgressReporter>,
+    ) -> Result<Vec<ContributionResponse>, Phase1Error> {
+        if chunks.start > chunks.end {
+            return Err(Phase1Error::InvalidParameters(format!(
+                "the chunk range {}..{} is reversed",
+                chunks.start, chunks.end
+            )));
+        }
+        for actual in [seeds.len(), challenges.len()] {
+            if actual != chunks.len() {
+                return Err(Phase1Error::ChunkCountMismatch {
+                    expected: chunks.len(),
+                    actual,
+                });
+            }
+        }
+
+        chunks
+            .zip(seeds)
+            .zip(challenges)
+            .map(|((chunk_index, seed), challenge)| {
+                contribute_in_pool(
+                    &self.thread_pool,
+                    curve_kind,
+                    proving_system,
+                    batch_size,
+                    power,
+                    chunk_index,
+                    chunk_size,
+                    seed,
+                    challenge,
+                    None,
+                    None,
+                    None,
+                    reporter.cloned(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Copies the response of `result` into `out`, which must have the same
+/// length, and returns `result` without it.
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+pub(crate) fn write_response(result: ContributionResponse, out: &js_sys::Uint8Array) -> ContributionResponse {
+    out.copy_from(&result.response);
+    ContributionResponse {
+        response: vec![],
+        ..result
+    }
+}
+
+/// Configures a rayon thread pool which will pull web workers from `worker`.
+#[cfg(all(not(test), not(feature = "single_threaded"), not(feature = "verify-only")))]
+fn build_thread_pool(
+    worker: &crate::pool::WorkerProcess,
+    thread_pool_size: usize,
+) -> Result<rayon::ThreadPool, Phase1Error> {
+    check_thread_pool_size(thread_pool_size)?;
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(thread_pool_size)
+        .spawn_handler(|thread| {
+            worker
+                .run(|| thread.run())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("{:?}", e)))
+        })
+        .build()
+        .map_err(|e| Phase1Error::WorkerPool(e.to_string()))
+}
+
+/// Rejects an empty thread pool, which rayon would otherwise size to the number of CPUs.
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+fn check_thread_pool_size(thread_pool_size: usize) -> Result<(), Phase1Error> {
+    if thread_pool_size == 0 {
+        return Err(Phase1Error::WorkerPool(
+            "the thread pool needs at least one thread".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Runs `fill` on another thread of the current thread pool while `generate`
+/// runs on this one, where there is another thread: natively, and on WASM on
+/// the workers of a `Phase1Pool`. Elsewhere, `fill` runs first.
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+fn fill_alongside<R>(fill: impl FnOnce() + Send, generate: impl FnOnce() -> R) -> R {
+    if cfg!(not(target_arch = "wasm32")) || rayon::current_thread_index().is_some() {
+        rayon::in_place_scope(|scope| {
+            scope.spawn(|_| fill());
+            generate()
+        })
+    } else {
+        fill();
+        generate()
+    }
+}
+
+/// Runs `fill` and then `generate`, as there is no other thread to fill on
+/// with the `single_threaded` feature.
+#[cfg(all(feature = "single_threaded", not(feature = "verify-only")))]
+fn fill_alongside<R>(fill: impl FnOnce() + Send, generate: impl FnOnce() -> R) -> R {
+    fill();
+    generate()
+}
+
+#[cfg(all(not(feature = "single_threaded"), not(feature = "verify-only")))]
+#[allow(clippy::too_many_arguments)]
+fn contribute_in_pool(
+    thread_pool: &rayon::ThreadPool,
+    curve_kind: &str,
+    proving_system: &str,
+    batch_size: usize,
+    power: usize,
+    chunk_index: usize,
+    chunk_size: usize,
+    seed: &[u8],
+    challenge: Vec<u8>,
+    serialization: Option<SerializationOptions>,
+    progress: Option<js_sys::Function>,
+    cancel: Option<CancelToken>,
+    reporter: Option<ProgressReporter>,
+) -> Result<ContributionResponse, Phase1Error> {
+    let rng = rng_from_seed(seed)?;
+    // The names are checked before the contribution is handed to the pool.
+    proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+    curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?;
+
+    // The JS callback can't be called from the web workers, so progress
+    // is only reported from this thread before and after the contribution,
+    // and throughout it to the reporter.
+    report_progress(&progress, 0.0);
+
+    let (tx, rx) = oneshot::channel();
+    thread_pool.install(|| {
+        let mut unit = reporter.as_ref().map(|reporter| reporter.unit());
+        let options = ContributionOptions {
+            serialization: serialization.unwrap_or_default(),
+            progress: unit.as_mut().map(|unit| unit as &mut dyn FnMut(f64)),
+            cancel: cancel.as_ref(),
+            ..Default::default()
+        };
+        let res = contribute_chunk_with_options(
+            curve_kind,
+            proving_system,
+            batch_size,
+            power,
+            chunk_index,
+            chunk_size,
+            &challenge,
+            rng,
+            options,
+        );
+        drop(tx.send(res));
+    });
+
+    // The sender is dropped without sending if the contribution never completed.
+    let res = rx.recv().map_err(|_| Phase1Error::Channel)?;
+    if res.is_ok() {
+        report_progress(&progress, 1.0);
+    }
+    res
+}
+
+/// Contributes to a single chunk on the calling thread, as `contribute_chunked`
+/// does with the `single_threaded` feature.
+#[cfg(all(not(test), feature = "single_threaded", not(feature = "verify-only")))]
+#[allow(clippy::too_many_arguments)]
+fn contribute_chunk_inline(
+    curve_kind: &str,
+    proving_system: &str,
+    batch_size: usize,
+    power: usize,
+    chunk_index: usize,
+    chunk_size: usize,
+    seed: &[u8],
+    challenge: Vec<u8>,
+    serialization: Option<SerializationOptions>,
+    progress: Option<js_sys::Function>,
+    cancel: Option<CancelToken>,
+    reporter: Option<ProgressReporter>,
+) -> Result<ContributionResponse, Phase1Error> {
+    let rng = rng_from_seed(seed)?;
+
+    // On the calling thread, the JS callback can be called throughout the contribution.
+    let mut unit = reporter.as_ref().map(|reporter| reporter.unit());
+    let mut report = |fraction: f64| {
+        report_progress(&progress, fraction);
+        if let Some(unit) = unit.as_mut() {
+            unit(fraction);
+        }
+    };
+    let options = ContributionOptions {
+        serialization: serialization.unwrap_or_default(),
+        progress: Some(&mut report),
+        cancel: cancel.as_ref(),
+        ..Default::default()
+    };
+
+    contribute_chunk_with_options(
+        curve_kind,
+        proving_system,
+        batch_size,
+        power,
+        chunk_index,
+        chunk_size,
+        &challenge,
+        rng,
+        options,
+    )
+}
+
+/// Contributes to the chunk `chunk_index` on the calling thread.
+#[cfg(not(feature = "verify-only"))]
+#[allow(clippy::too_many_arguments)]
+fn contribute_chunk_with_options(
+    curve_kind: &str,
+    proving_system: &str,
+    batch_size: usize,
+    power: usize,
+    chunk_index: usize,
+    chunk_size: usize,
+    challenge: &[u8],
+    rng: impl Rng + CryptoRng,
+    options: ContributionOptions,
+) -> Result<ContributionResponse, Phase1Error> {
+    let proving_system = proving_system_from_str(proving_system).map_err(Phase1Error::InvalidProvingSystem)?;
+    let (chunk_index, chunk_size) = (Some(chunk_index), Some(chunk_size));
+    dispatch_curve!(curve_from_str(curve_kind).map_err(Phase1Error::InvalidCurve)?, |E| {
+        contribute_challenge_with_options(
+            challenge,
+            &get_parameters::<E>(proving_system, power, batch_size, chunk_index, chunk_size)?,
+            rng,
+            options,
+        )
+    })
+}
+
+/// Gathers the entropy a contribution's RNG is seeded with, as
+/// `setup_utils::user_system_randomness` does in the browser.
+///
+/// With the `wasi` feature, it is 1024 bytes from `getrandom` instead, which
+/// reads them from the `random_get` of the WASI host, as there's no user to ask
+/// for more and reading stdin may block. The host must be on a WASI preview
+/// which provides `random_get`.
+#[cfg(not(feature = "verify-only"))]
+pub fn system_randomness() -> Result<Vec<u8>, Phase1Error> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "wasi")] {
+            let mut entropy = vec![0; 1024];
+            getrandom::getrandom(&mut entropy).map_err(|e| Phase1Error::Entropy(e.to_string()))?;
+            Ok(calculate_hash(&entropy).to_vec())
+        } else {
+            Ok(setup_utils::user_system_randomness())
+        }
+    }
+}
+
+/// Derives the seed of a beacon contribution by hashing `beacon_hash` with
+/// SHA-256 `iterations` times, as `setup_utils::beacon_randomness` does.
+pub fn beacon_seed(beacon_hash: &[u8], iterations: u32) -> Vec<u8> {
+    iterated_hash(beacon_hash, iterations)
+}
+
+/// Hashes `input` with SHA-256 `rounds` times, returning `input` unchanged
+/// if `rounds` is zero. This is the hash beacon seeds are derived with, so a
+/// verifier can derive them independently.
+#[wasm_bindgen]
+pub fn iterated_hash(input: &[u8], rounds: u32) -> Vec<u8> {
+    let mut digest = input.to_vec();
+    for _ in 0..rounds {
+        digest = Sha256::digest(&digest).to_vec();
+    }
+    digest
+}
+
+/// Hashes `data` as challenges, responses and contributions are hashed
+/// throughout the ceremony, with unkeyed, unpersonalized Blake2b-512, so the
+/// digest is `HASH_LENGTH` (64) bytes long. Tooling hashing data meant to
+/// match the hashes of the ceremony should use this rather than its own Blake2b.
+#[wasm_bindgen]
+pub fn hash_bytes(data: &[u8]) -> Vec<u8> {
+    calculate_hash(data).to_vec()
+}
+
+/// The size in bytes of a page of WASM memory, which memory grows by.
+#[cfg(target_arch = "wasm32")]
+const WASM_PAGE_SIZE: usize = 1 << 16;
+
+/// Grows WASM memory with `memory.grow` by enough pages for `bytes` up front,
+/// such as the `estimate_memory` of a contribution, so its buffers don't grow
+/// memory again and again as they are allocated. Fails without growing it if
+/// the environment can't grow memory that far. Outside WASM, memory isn't
+/// grown in pages, so this does nothing.
+#[wasm_bindgen]
+pub fn reserve_memory(bytes: usize) -> Result<(), Phase1Error> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let pages = (bytes / WASM_PAGE_SIZE) + (bytes % WASM_PAGE_SIZE != 0) as usize;
+        // `memory.grow` returns the previous number of pages, or -1 when it fails.
+        if std::arch::wasm32::memory_grow(0, pages) == usize::MAX {
+            return Err(Phase1Error::MemoryReservation(format!(
+                "can't grow memory by {} pages of {} bytes",
+                pages, WASM_PAGE_SIZE
+            )));
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = bytes;
+    Ok(())
+}
+
+/// The seed of the contributions `self_test` makes.
+#[cfg(not(feature = "verify-only"))]
+const SELF_TEST_SEED: [u8; MIN_SEED_LENGTH] = [0; MIN_SEED_LENGTH];
+
+/// A curve compiled into this build, which is BLS12-377 unless the build leaves
+/// it out, for `self_test` and for the parameters which don't depend on the curve.
+#[cfg(feature = "curve-bls12_377")]
+const COMPILED_CURVE: CurveKind = CurveKind::Bls12_377;
+#[cfg(not(feature = "curve-bls12_377"))]
+const COMPILED_CURVE: CurveKind = CurveKind::BW6;
+
+/// Contributes at the smallest power on `COMPILED_CURVE` with a fixed seed, to
+/// catch a miscompiled or corrupted build before it's trusted with a contribution.
+/// The contribution must verify, which exercises the curve arithmetic and the
+/// pairings.
+#[cfg(not(feature = "verify-only"))]
+#[wasm_bindgen]
+pub fn self_test() -> Result<(), JsValue> {
+    dispatch_curve!(COMPILED_CURVE, |E| {
+        let parameters = get_parameters_full::<E>(ProvingSystem::Groth16, MIN_POWER, 2);
+        let challenge = initial_challenge(&parameters)?;
+
+        let result = contribute_challenge(&challenge, &parameters, derive_rng_from_seed(&SELF_TEST_SEED))?;
+        if !verify_response(&challenge, &result.response, &parameters)? {
+            return Err(JsValue::from_str("the contribution does not verify"));
+        }
+        Ok(())
+    })
+}
+
+/// The domain separator of the seeds `derive_chunk_seed` derives, so they
+/// never collide with another hash of the master seed.
+const CHUNK_SEED_DOMAIN: &[u8] = b"aleo-setup phase1 chunk seed";
+
+/// Derives the seed of the chunk `chunk_index` from `master_seed`, as the
+/// SHA-256 hash of a domain separator, the master seed and the index as 8
+/// little-endian bytes. Each chunk gets a distinct seed, so one secret can seed
+/// every chunk without reusing randomness. The master seed should be at least
+/// `MIN_SEED_LENGTH` bytes long, which the derived seed always is.
+#[wasm_bindgen]
+pub fn derive_chunk_seed(master_seed: &[u8], chunk_index: usize) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(CHUNK_SEED_DOMAIN);
+    hasher.update(master_seed);
+    hasher.update((chunk_index as u64).to_le_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Derives the contribution RNG from `seed`, rejecting seeds shorter than
+/// `MIN_SEED_LENGTH` bytes, which would make the RNG weak.
+#[cfg(not(feature = "verify-only"))]
+fn rng_from_seed(seed: &[u8]) -> Result<impl Rng + CryptoRng, Phase1Error> {
+    if seed.len() < MIN_SEED_LENGTH {
+        return Err(Phase1Error::SeedTooShort { len: seed.len() });
+    }
+    Ok(derive_rng_from_seed(seed))
+}
+
+/// Reports `fraction` to the JS `progress` callback, if one was given.
+#[cfg(not(feature = "verify-only"))]
+fn report_progress(progress: &Option<js_sys::Function>, fraction: f64) {
+    if let Some(progress) = progress {
+        drop(progress.call1(&JsValue::NULL, &JsValue::from_f64(fraction)));
+    }
+}
+
+// The global `fetch`, which windows and web workers both have.
+#[cfg(all(target_arch = "wasm32", not(feature = "verify-only")))]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_url(url: &str) -> js_sys::Promise;
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "verify-only")))]
+fn fetch_error(error: JsValue) -> Phase1Error {
+    Phase1Error::Fetch(format!("{:?}", error))
+}
+
+/// Reads the body of `response`, which must be a challenge `expected_length`
+/// long, chunk by chunk into a buffer allocated once at that length. A body
+/// longer than that is rejected as soon as the excess arrives.
+#[cfg(all(target_arch = "wasm32", not(feature = "verify-only")))]
+pub(crate) async fn read_challenge(
+    response: web_sys::Response,
+    expected_length: usize,
+) -> Result<Vec<u8>, Phase1Error> {
+    use wasm_bindgen::JsCast;
+
+    if response.status() != 200 {
+        return Err(Phase1Error::FetchStatus {
+            status: response.status(),
+        });
+    }
+    // The `Content-Length` of an encoded body, such as a gzipped one, is the
+    // length of the encoded bytes rather than of the challenge, so it's ignored.
+    let headers = response.headers();
+    let encoded = headers.get("Content-Encoding").map_err(fetch_error)?.is_some();
+    let content_length = headers
+        .get("Content-Length")
+        .map_err(fetch_error)?
+        .filter(|_| !encoded)
+        .and_then(|length| length.trim().parse::<usize>().ok());
+    if let Some(content_length) = content_length {
+        if content_length != expected_length {
+            return Err(Phase1Error::ChallengeLength {
+                expected: expected_length,
+                actual: content_length,
+            });
+        }
+    }
+
+    let body = response.body().ok_or(Phase1Error::EmptyChallenge)?;
+    let reader: web_sys::ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+    let mut challenge = Vec::with_capacity(expected_length);
+    loop {
+        let read = wasm_bindgen_futures::JsFuture::from(reader.read())
+            .await
+            .map_err(fetch_error)?;
+        let done = js_sys::Reflect::get(&read, &JsValue::from_str("done")).map_err(fetch_error)?;
+        if done.as_bool().unwrap_or(true) {
+            break;
+        }
+        let chunk: js_sys::Uint8Array = js_sys::Reflect::get(&read, &JsValue::from_str("value"))
+            .map_err(fetch_error)?
+            .unchecked_into();
+
+        let (start, received) = (challenge.len(), challenge.len() + chunk.length() as usize);
+        if received > expected_length {
+            drop(reader.cancel());
+            return Err(Phase1Error::ChallengeLength {
+                expected: expected_length,
+                actual: received,
+            });
+        }
+        challenge.resize(received, 0);
+        chunk.copy_to(&mut challenge[start..]);
+    }
+
+    // The download can end early, such as when the connection drops.
+    if let Some(content_length) = content_length {
+        if challenge.len() != content_length {
+            return Err(Phase1Error::DownloadLength {
+                expected: content_length,
+                actual: challenge.len(),
+            });
+        }
+    }
+    if challenge.is_empty() {
+        return Err(Phase1Error::EmptyChallenge);
+    }
+    if challenge.len() != expected_length {
+        return Err(Phase1Error::ChallengeLength {
+            expected: expected_length,
+            actual: challenge.len(),
+        });
+    }
+    Ok(challenge)
+}
+
+pub fn get_parameters_full<E: PairingEngine>(
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+) -> Phase1Parameters<E> {
+    Phase1Parameters::<E>::new_full(proving_system, power, batch_size)
+}
+
+/// Returns the parameters of the chunk `chunk_index`, without checking that
+/// the chunk exists. The entry points go through `get_parameters`, which
+/// returns `Phase1Error::ChunkIndexOutOfRange` for a nonexistent chunk.
+pub fn get_parameters_chunked<E: PairingEngine>(
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk_index: usize,
+    chunk_size: usize,
+) -> Phase1Parameters<E> {
+    get_parameters_chunked_with_mode(
+        ContributionMode::Chunked,
+        proving_system,
+        power,
+        batch_size,
+        chunk_index,
+        chunk_size,
+    )
+}
+
+/// Returns the parameters of the chunk `chunk_index` like `get_parameters_chunked`,
+/// but in the contribution mode `mode`. In `ContributionMode::Full` the only
+/// chunk is the full ceremony, whose index is 0, so a full ceremony can be
+/// contributed to as a job of one chunk.
+pub fn get_parameters_chunked_with_mode<E: PairingEngine>(
+    mode: ContributionMode,
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk_index: usize,
+    chunk_size: usize,
+) -> Phase1Parameters<E> {
+    Phase1Parameters::<E>::new_chunk(mode, chunk_index, chunk_size, proving_system, power, batch_size)
+}
+
+/// Returns the parameters of a single chunk when both `chunk_index` and
+/// `chunk_size` are given, and of the full ceremony otherwise, after checking
+/// them with `validate_parameters`. A full ceremony must be of a power up to
+/// `MAX_SUPPORTED_POWER`.
+pub(crate) fn get_parameters<E: PairingEngine>(
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk_index: Option<usize>,
+    chunk_size: Option<usize>,
+) -> Result<Phase1Parameters<E>, Phase1Error> {
+    get_parameters_with_max_power(
+        proving_system,
+        power,
+        batch_size,
+        chunk_index,
+        chunk_size,
+        MAX_SUPPORTED_POWER,
+    )
+}
+
+/// Returns the parameters like `get_parameters`, for a full ceremony of a
+/// power up to `max_power`, which must also fit in memory on WASM. This is
+/// checked before the parameters are built, as their sizes overflow on WASM
+/// for the largest powers. A chunk only holds `chunk_size` elements, so the
+/// parameters of a chunk are only checked by `validate_parameters`.
+pub(crate) fn get_parameters_with_max_power<E: PairingEngine>(
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk_index: Option<usize>,
+    chunk_size: Option<usize>,
+    max_power: usize,
+) -> Result<Phase1Parameters<E>, Phase1Error> {
+    let full = chunk_index.is_none() || chunk_size.is_none();
+    if full && power > max_power {
+        return Err(Phase1Error::PowerTooLarge {
+            requested: power,
+            max: max_power,
+        });
+    }
+    validate_parameters(proving_system, power, batch_size, chunk_index.and(chunk_size))?;
+
+    match (chunk_index, chunk_size) {
+        (Some(chunk_index), Some(chunk_size)) => {
+            let num_chunks = num_chunks(proving_system, power, chunk_size)?;
+            if chunk_index >= num_chunks {
+                return Err(Phase1Error::ChunkIndexOutOfRange {
+                    index: chunk_index,
+                    num_chunks,
+                });
+            }
+            Ok(get_parameters_chunked(
+                proving_system,
+                power,
+                batch_size,
+                chunk_index,
+                chunk_size,
+            ))
+        }
+        _ => {
+            let parameters = get_parameters_full(proving_system, power, batch_size);
+            check_fits_in_memory(&parameters)?;
+            Ok(parameters)
+        }
+    }
+}
+
+/// Checks that a full contribution with `parameters` fits in `WASM_MEMORY_LIMIT`
+/// on WASM, returning the largest power which does otherwise. Native memory isn't limited.
+fn check_fits_in_memory<E: PairingEngine>(parameters: &Phase1Parameters<E>) -> Result<(), Phase1Error> {
+    let fits = |parameters: &Phase1Parameters<E>| {
+        cfg!(not(target_arch = "wasm32")) || estimate_contribution_memory(parameters) <= WASM_MEMORY_LIMIT
+    };
+    if fits(parameters) {
+        return Ok(());
+    }
+
+    let requested = parameters.total_size_in_log2;
+    let max = (MIN_POWER..requested)
+        .rev()
+        .find(|&power| {
+            fits(&get_parameters_full(
+                parameters.proving_system,
+                power,
+                parameters.batch_size,
+            ))
+        })
+        .unwrap_or(0);
+    Err(Phase1Error::PowerTooLarge { requested, max })
+}
+
+/// Checks that the ceremony parameters are ones `Phase1Parameters` supports,
+/// before anything is allocated for them:
+///
+/// + `power` is between `MIN_POWER` and `MAX_POWER`, since Marlin's extra
+///   elements are indexed by `power` and larger powers overflow the buffers
+/// + `batch_size` is nonzero, since the accumulator is processed in batches of it
+/// + `chunk_size`, when given, is nonzero. It needn't divide the number of
+///   elements, since the last chunk holds whatever remains
+/// + for Marlin, `chunk_size` is larger than `3 + 3 * power`, so that the
+///   extra alpha and tau_g2 elements fit in the first chunk
+pub fn validate_parameters(
+    proving_system: ProvingSystem,
+    power: usize,
+    batch_size: usize,
+    chunk_size: Option<usize>,
+) -> Result<(), Phase1Error> {
+    if !(MIN_POWER..=MAX_POWER).contains(&power) {
+        return Err(Phase1Error::InvalidParameters(format!(
+            "the power should be between {} and {}, but it's {}",
+            MIN_POWER, MAX_POWER, power
+        )));
+    }
+    if batch_size == 0 {
+        return Err(Phase1Error::InvalidParameters(
+            "the batch size should be nonzero".to_string(),
+        ));
+    }
+    match (proving_system, chunk_size) {
+        (_, Some(0)) => Err(Phase1Error::InvalidParameters(
+            "the chunk size should be nonzero".to_string(),
+        )),
+        (ProvingSystem::Marlin, Some(chunk_size)) if chunk_size <= 3 + 3 * power => {
+            Err(Phase1Error::InvalidParameters(format!(
+                "the chunk size of a Marlin ceremony should be larger than {}, but it's {}",
+                3 + 3 * power,
+                chunk_size
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the number of chunks a ceremony of `2^power` powers is split into
+/// when each chunk holds `chunk_size` elements, which must not be zero.
+pub fn num_chunks(proving_system: ProvingSystem, power: usize, chunk_size: usize) -> Result<usize, Phase1Error> {
+    if chunk_size == 0 {
+        return Err(Phase1Error::InvalidParameters(
+            "the chunk size should be nonzero".to_string(),
+        ));
+    }
+
+    // 2^{size}
+    let powers_length = 1 << power;
+    // 2^{size+1} - 1
+    let powers_g1_length = (powers_length << 1) - 1;
+
+    let upper_bound = match proving_system {
+        ProvingSystem::Groth16 => powers_g1_length,
+        ProvingSystem::Marlin => powers_length,
+    };
+
+    Ok((upper_bound + chunk_size - 1) / chunk_size)
+}
+
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute_challenge<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+) -> Result<ContributionResponse, Phase1Error> {
+    let mut response = vec![];
+    let result = contribute_challenge_into(challenge, parameters, rng, &mut response)?;
+    Ok(ContributionResponse { response, ..result })
+}
+
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute_challenge_with_options<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+    options: ContributionOptions,
+) -> Result<ContributionResponse, Phase1Error> {
+    let mut response = vec![];
+    let result = contribute_challenge_into_with_options(challenge, parameters, rng, options, &mut response)?;
+    Ok(ContributionResponse { response, ..result })
+}
+
+/// Verifies `prior_response` as a contribution to `prior_challenge` with
+/// `verify_response`, and only if it is valid, contributes to the challenge
+/// `next_challenge` builds from it. An invalid prior contribution is
+/// `Phase1Error::PriorContributionInvalid`, and nothing is contributed.
+#[cfg(not(feature = "verify-only"))]
+pub fn verify_then_contribute<E: PairingEngine + Sync>(
+    prior_challenge: &[u8],
+    prior_response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+) -> Result<ContributionResponse, Phase1Error> {
+    if !verify_response(prior_challenge, prior_response, parameters)? {
+        return Err(Phase1Error::PriorContributionInvalid);
+    }
+    contribute_challenge(&next_challenge(prior_response, parameters)?, parameters, rng)
+}
+
+/// Contributes like `contribute_challenge`, but writes the response into the
+/// caller's `response` buffer so it can be reused across contributions. The
+/// buffer is cleared and resized to the required output length, and the
+/// `response` of the returned `ContributionResponse` is left empty.
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute_challenge_into<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+    response: &mut Vec<u8>,
+) -> Result<ContributionResponse, Phase1Error> {
+    contribute_challenge_into_with_options(challenge, parameters, rng, ContributionOptions::default(), response)
+}
+
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute_challenge_into_with_options<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+    options: ContributionOptions,
+    response: &mut Vec<u8>,
+) -> Result<ContributionResponse, Phase1Error> {
+    let hash_algorithm = options.hash_algorithm;
+    contribute_challenge_then(challenge, parameters, rng, options, response, |response, _| {
+        Ok(hash_algorithm.hash(response))
+    })
+}
+
+/// Contributes like `contribute_challenge_into_with_options`, but leaves the
+/// contribution hash to `finish`, which is handed the finished response and
+/// the length of the public key at its end.
+#[cfg(not(feature = "verify-only"))]
+fn contribute_challenge_then<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    mut rng: impl Rng + CryptoRng,
+    options: ContributionOptions,
+    response: &mut Vec<u8>,
+    finish: impl FnOnce(&[u8], usize) -> Result<Vec<u8>, Phase1Error>,
+) -> Result<ContributionResponse, Phase1Error> {
+    // An empty challenge is most likely a failed download, so it's reported
+    // as such before it's hashed, rather than as a challenge of the wrong length.
+    if challenge.is_empty() {
+        return Err(Phase1Error::EmptyChallenge);
+    }
+
+    let mut progress = options.progress;
+    let mut report_progress = |fraction: f64| {
+        if let Some(progress) = progress.as_mut() {
+            progress(fraction);
+        }
+    };
+    let cancel = options.cancel;
+    let strict = options.strict;
+    let mut checkpoint = options.checkpoint;
+    let resume = options.resume;
+    let key_generation_attempts = options.key_generation_attempts;
+    let mut timing = ContributionTiming::default();
+    let SerializationOptions {
+        compressed_input,
+        compressed_output,
+        check_input,
+    } = options.serialization;
+    let check_cancelled = || match cancel {
+        Some(cancel) if cancel.is_cancelled() => Err(Phase1Error::Cancelled),
+        _ => Ok(()),
+    };
+
+    // `Phase1::computation` panics on invalid elements, so check them up front.
+    check_challenge(challenge, parameters, options.serialization)?;
+    log::debug!("Checked the challenge of {} bytes", challenge.len());
+
+    // The estimate is conservative, so the contribution may still fit in
+    // memory when it can't all be reserved.
+    #[cfg(target_arch = "wasm32")]
+    if options.reserve_memory {
+        reserve_memory(estimate_contribution_memory(parameters).saturating_sub(challenge.len())).ok();
+    }
+
+    let public_key_compression = options.public_key_compression;
+    let required_output_length =
+        contribution_core::response_len_with_public_key(parameters, compressed_output, public_key_compression);
+
+    let stopwatch = Stopwatch::start();
+    let current_accumulator_hash = match options.current_accumulator_hash {
+        Some(hash) if hash.len() != options.hash_algorithm.digest_len() => {
+            return Err(Phase1Error::InvalidParameters(format!(
+                "the challenge hash is {} bytes, but a {:?} hash is {}",
+                hash.len(),
+                options.hash_algorithm,
+                options.hash_algorithm.digest_len()
+            )));
+        }
+        Some(hash) => hash.to_vec(),
+        None => options.hash_algorithm.hash(challenge),
+    };
+    let transcript_hash = contribution_core::transcript_hash(&current_accumulator_hash);
+    timing.hash_ms += stopwatch.elapsed_ms();
+    log::debug!("Computed the challenge hash {}", hex::encode(&current_accumulator_hash));
+
+    if let Some(resume) = resume {
+        if !contribution_core::hashes_eq(&resume.current_accumulator_hash(), &current_accumulator_hash) {
+            return Err(Phase1Error::Checkpoint(
+                "the checkpoint is of a contribution to another challenge".to_string(),
+            ));
+        }
+    }
+
+    check_cancelled()?;
+    report_progress(0.0);
+    // Construct our keypair using the RNG we created above
+    let generate_keypair = || -> Result<(phase1::PublicKey<E>, phase1::PrivateKey<E>), Phase1Error> {
+        let stopwatch = Stopwatch::start();
+        let keypair = match resume {
+            Some(resume) => resume.keypair()?,
+            None => {
+                contribution_core::generate_keypair_with_attempts(&mut rng, &transcript_hash, key_generation_attempts)?
+            }
+        };
+        timing.key_generation_ms = stopwatch.elapsed_ms();
+        Ok(keypair)
+    };
+    let fill = options.fill;
+    // The keypair only depends on the hash, so the response can be filled at the same time.
+    let (public_key, private_key) = fill_alongside(
+        || fill_response(response, &transcript_hash, required_output_length, fill),
+        generate_keypair,
+    )?;
+    let private_key = contribution_core::SecretKey::from(private_key);
+    log::debug!("Generated the keypair");
+    report_progress(0.1);
+    if let (Some(checkpoint), None) = (checkpoint.as_mut(), resume) {
+        checkpoint(Checkpoint::new(
+            CheckpointStage::KeyGenerated,
+            &current_accumulator_hash,
+            &public_key,
+            &private_key,
+            &[],
+        )?);
+    }
+    report_progress(0.2);
+
+    check_cancelled()?;
+    let stopwatch = Stopwatch::start();
+    match resume.and_then(|resume| resume.response()) {
+        Some(computed) => {
+            if computed.len() != required_output_length {
+                return Err(Phase1Error::Checkpoint(
+                    "the checkpoint's response has the wrong length".to_string(),
+                ));
+            }
+            response.clear();
+            response.extend_from_slice(computed);
+        }
+        None => {
+            // This computes a transformation and writes it
+            contribution_core::compute(
+                &challenge,
+                response,
+                compressed_input,
+                compressed_output,
+                check_input,
+                &private_key,
+                &parameters,
+            )?;
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint(Checkpoint::new(
+                    CheckpointStage::Computed,
+                    &current_accumulator_hash,
+                    &public_key,
+                    &private_key,
+                    response,
+                )?);
+            }
+        }
+    }
+    timing.computation_ms = stopwatch.elapsed_ms();
+    log::info!("Completed the computation of the contribution");
+    report_progress(0.9);
+
+    check_cancelled()?;
+    let stopwatch = Stopwatch::start();
+    let public_key_bytes = contribution_core::write_public_key_with_compression(
+        response,
+        &public_key,
+        compressed_output,
+        public_key_compression,
+        &parameters,
+        strict,
+    )?;
+    timing.write_ms = stopwatch.elapsed_ms();
+    log::debug!("Wrote the public key");
+
+    let stopwatch = Stopwatch::start();
+    let contribution_hash = finish(response, public_key_bytes.len())?;
+    timing.hash_ms += stopwatch.elapsed_ms();
+    report_progress(1.0);
+
+    Ok(ContributionResponse {
+        format_magic: response_format_magic(),
+        format_version: RESPONSE_FORMAT_VERSION,
+        current_accumulator_hash,
+        response: vec![],
+        contribution_hash,
+        public_key: public_key_bytes,
+        beacon_seed: None,
+        randomness_commitment: None,
+        timing: if options.timing { Some(timing) } else { None },
+    })
+}
+
+/// Contributes like `contribute_challenge`, but hands the response to `sink` in
+/// order, the accumulator followed by the public key, instead of returning it.
+/// The contribution hash is computed over the slices as they are handed out.
+///
+/// `Phase1::computation` transforms the accumulator in place, so the response
+/// is still built in memory, but the caller never holds a copy of it and it is
+/// freed as soon as it has been streamed.
+#[cfg(not(feature = "verify-only"))]
+pub fn contribute_challenge_streaming<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    rng: impl Rng + CryptoRng,
+    mut sink: impl FnMut(&[u8]) -> Result<(), String>,
+) -> Result<ContributionResponse, Phase1Error> {
+    let mut response = vec![];
+    contribute_challenge_then(
+        challenge,
+        parameters,
+        rng,
+        ContributionOptions::default(),
+        &mut response,
+        |response, public_key_len| {
+            let (accumulator, public_key) = response.split_at(response.len() - public_key_len);
+            let mut hasher = Blake2b::default();
+            for slice in accumulator.chunks(STREAM_SLICE_SIZE).chain(std::iter::once(public_key)) {
+                hasher.update(slice);
+                sink(slice).map_err(Phase1Error::Sink)?;
+            }
+            Ok(hasher.finalize().to_vec())
+        },
+    )
+}
+
+/// Returns an approximate, conservative estimate of the peak memory in bytes a
+/// contribution with `parameters` needs. The model counts:
+///
+/// + the uncompressed challenge and the response buffer, held for the whole contribution
+/// + the working set of `Phase1::computation`, which for each batch reads up to
+///   `batch_size` elements of each of tau_g1, tau_g2, alpha_g1 and beta_g1,
+///   counted three times over for their affine and projective copies, plus the
+///   `batch_size` powers of tau they are raised to
+///
+/// Allocator overhead and the size of the WASM module itself are not included.
+pub fn estimate_contribution_memory<E: PairingEngine>(parameters: &Phase1Parameters<E>) -> usize {
+    let batch_size = std::cmp::min(parameters.batch_size, parameters.powers_g1_length);
+    buffers_memory(parameters) + batch_size * batch_element_memory(parameters)
+}
+
+/// Returns the largest batch size with which a contribution with `parameters`
+/// fits in `available_memory` bytes by `estimate_contribution_memory`, clamped
+/// between `MIN_SUGGESTED_BATCH_SIZE` and `MAX_SUGGESTED_BATCH_SIZE`. The
+/// `batch_size` of `parameters` is ignored, and the batch is never larger
+/// than the tau_g1 powers, as a larger one uses no more memory but gains nothing.
+///
+/// When even the smallest batch doesn't fit, the smallest is still returned,
+/// as the response buffers alone are already over budget.
+pub fn suggest_batch_size_for<E: PairingEngine>(parameters: &Phase1Parameters<E>, available_memory: usize) -> usize {
+    let fitting = available_memory.saturating_sub(buffers_memory(parameters)) / batch_element_memory(parameters);
+    std::cmp::min(
+        fitting.clamp(MIN_SUGGESTED_BATCH_SIZE, MAX_SUGGESTED_BATCH_SIZE),
+        parameters.powers_g1_length,
+    )
+}
+
+/// Returns the number of batches a full contribution with `parameters` is computed in.
+pub fn num_batches<E: PairingEngine>(parameters: &Phase1Parameters<E>) -> usize {
+    (parameters.powers_g1_length + parameters.batch_size - 1) / parameters.batch_size
+}
+
+/// Contributes to an initial challenge for `parameters`, and returns how long
+/// the contribution took in milliseconds. The key is thrown away, so it is
+/// generated from a fixed seed.
+#[cfg(not(feature = "verify-only"))]
+fn time_contribution<E: PairingEngine + Sync>(parameters: &Phase1Parameters<E>) -> Result<f64, Phase1Error> {
+    let challenge = initial_challenge(parameters)?;
+
+    let stopwatch = Stopwatch::start();
+    contribute_challenge(&challenge, parameters, derive_rng_from_seed(&[0; MIN_SEED_LENGTH]))?;
+    Ok(stopwatch.elapsed_ms())
+}
+
+/// The memory of the challenge and response buffers, for `estimate_contribution_memory`.
+fn buffers_memory<E: PairingEngine>(parameters: &Phase1Parameters<E>) -> usize {
+    parameters.accumulator_size + parameters.contribution_size
+}
+
+/// The memory each element of a batch takes, for `estimate_contribution_memory`.
+fn batch_element_memory<E: PairingEngine>(parameters: &Phase1Parameters<E>) -> usize {
+    (3 * parameters.curve.g1_size + parameters.curve.g2_size) * 3 + std::mem::size_of::<E::Fr>()
+}
+
+/// Checks that `challenge` has the length `serialization` expects for
+/// `parameters`, and that its elements are valid when input checks are enabled.
+pub fn check_challenge<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+    serialization: SerializationOptions,
+) -> Result<(), Phase1Error> {
+    contribution_core::check_challenge(
+        challenge,
+        parameters,
+        serialization.compressed_input,
+        serialization.check_input,
+    )
+}
+
+/// Verifies that `response` is a valid contribution on top of `challenge`,
+/// returning `false` if the hash chain or any of the `Phase1::verification`
+/// checks fail.
+pub fn verify_response<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+) -> Result<bool, Phase1Error> {
+    verify_response_with_options(challenge, response, parameters, SerializationOptions::default())
+}
+
+pub fn verify_response_with_options<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    serialization: SerializationOptions,
+) -> Result<bool, Phase1Error> {
+    verify_response_with_hash(challenge, response, parameters, serialization, HashAlgorithm::default())
+}
+
+/// Verifies `response` like `verify_response_with_options`, for a ceremony
+/// which hashes challenges with `hash_algorithm`.
+pub fn verify_response_with_hash<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    serialization: SerializationOptions,
+    hash_algorithm: HashAlgorithm,
+) -> Result<bool, Phase1Error> {
+    let SerializationOptions {
+        compressed_input,
+        compressed_output,
+        check_input,
+    } = serialization;
+
+    if challenge.len() != expected_challenge_len(parameters, compressed_input) {
+        return Err(contribution_core::challenge_length_error(
+            parameters,
+            compressed_input,
+            challenge.len(),
+        ));
+    }
+
+    let required_output_length = contribution_core::response_len(parameters, compressed_output);
+
+    if response.len() != required_output_length {
+        return Err(Phase1Error::ResponseLength {
+            expected: required_output_length,
+            actual: response.len(),
+        });
+    }
+
+    let current_accumulator_hash = contribution_core::transcript_hash(&hash_algorithm.hash(challenge));
+
+    // Check the hash chain - a new response must be based on the previous challenge!
+    if !contribution_core::hashes_eq(&response[0..parameters.hash_size], &current_accumulator_hash) {
+        return Ok(false);
+    }
+
+    let public_key = match phase1::PublicKey::<E>::read(&response, compressed_output, &parameters) {
+        Ok(public_key) => public_key,
+        Err(e) => return Err(Phase1Error::PublicKeyRead(e.to_string())),
+    };
+
+    match Phase1::verification(
+        &challenge,
+        &response,
+        &public_key,
+        current_accumulator_hash.as_slice(),
+        compressed_input,
+        compressed_output,
+        check_input,
+        CheckForCorrectness::Full,
+        &parameters,
+    ) {
+        Ok(_) => Ok(true),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Checks that `response` was contributed to `challenge`, without any of the
+/// group or pairing checks of a full verification: the hash of the challenge
+/// recorded before the accumulator must be that of `challenge`, and the public
+/// key at the end must be readable. A hash that doesn't match returns `false`.
+pub fn check_response_links<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_output: UseCompression,
+) -> Result<bool, Phase1Error> {
+    let required_output_length = contribution_core::response_len(parameters, compressed_output);
+
+    if response.len() != required_output_length {
+        return Err(Phase1Error::ResponseLength {
+            expected: required_output_length,
+            actual: response.len(),
+        });
+    }
+
+    if let Err(e) = phase1::PublicKey::<E>::read(response, compressed_output, parameters) {
+        return Err(Phase1Error::PublicKeyRead(e.to_string()));
+    }
+
+    Ok(contribution_core::hashes_eq(
+        &response[0..parameters.hash_size],
+        calculate_hash(challenge).as_slice(),
+    ))
+}
+
+/// Verifies `response` like `verify_response_with_options`, but runs each check
+/// on its own, so one failing doesn't stop the others, and reports each outcome
+/// instead of returning an error. A check which can't be run at all because
+/// the challenge or response is too short fails too.
+///
+/// The ratio checks read the response without checking that its points are in
+/// their subgroups, which is left to `subgroup_ok`.
+pub fn verify_detailed<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    serialization: SerializationOptions,
+) -> VerificationReport {
+    let SerializationOptions {
+        compressed_input,
+        compressed_output,
+        check_input,
+    } = serialization;
+
+    let challenge_length_ok = challenge.len() == expected_challenge_len(parameters, compressed_input);
+    let required_output_length = contribution_core::response_len(parameters, compressed_output);
+    let response_complete = response.len() >= required_output_length;
+    let current_accumulator_hash = calculate_hash(challenge);
+
+    let hash_link_ok = response.len() >= parameters.hash_size
+        && contribution_core::hashes_eq(&response[..parameters.hash_size], current_accumulator_hash.as_slice());
+
+    let public_key = match response_complete {
+        true => phase1::PublicKey::<E>::read(response, compressed_output, parameters).ok(),
+        false => None,
+    };
+
+    let subgroup_ok = public_key.is_some()
+        && Phase1::deserialize(
+            response,
+            compressed_output,
+            CheckForCorrectness::OnlyInGroup,
+            parameters,
+        )
+        .is_ok();
+
+    let ratio_check_ok = match &public_key {
+        Some(public_key) if challenge_length_ok => Phase1::verification(
+            challenge,
+            response,
+            public_key,
+            current_accumulator_hash.as_slice(),
+            compressed_input,
+            compressed_output,
+            check_input,
+            CheckForCorrectness::No,
+            parameters,
+        )
+        .is_ok(),
+        _ => false,
+    };
+
+    VerificationReport {
+        length_ok: challenge_length_ok && response.len() == required_output_length,
+        subgroup_ok,
+        ratio_check_ok,
+        hash_link_ok,
+    }
+}
+
+/// Reads the components of `response` without verifying it: the challenge
+/// hash it starts with, and the public key at its end, which must deserialize.
+/// Whether the accumulator and the public key are compressed is told from the
+/// length of the response, and a length matching none is `Phase1Error::Malformed`.
+pub fn parse_response<E: PairingEngine>(
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+) -> Result<ParsedResponse, Phase1Error> {
+    let (compressed, public_key_compression) = [
+        (UseCompression::Yes, UseCompression::Yes),
+        (UseCompression::No, UseCompression::Yes),
+        (UseCompression::Yes, UseCompression::No),
+        (UseCompression::No, UseCompression::No),
+    ]
+    .iter()
+    .copied()
+    .find(|&(compressed, public_key_compression)| {
+        response.len()
+            == contribution_core::response_len_with_public_key(parameters, compressed, public_key_compression)
+    })
+    .ok_or_else(|| {
+        Phase1Error::Malformed(format!(
+            "a response should be {} bytes long compressed, or {} uncompressed, but it's {}",
+            contribution_core::response_len(parameters, UseCompression::Yes),
+            contribution_core::response_len(parameters, UseCompression::No),
+            response.len()
+        ))
+    })?;
+
+    contribution_core::read_public_key(response, compressed, public_key_compression, parameters)
+        .map_err(|e| Phase1Error::Malformed(e.to_string()))?;
+
+    let (g1_size, g2_size) = match public_key_compression {
+        UseCompression::Yes => (parameters.curve.g1_compressed_size, parameters.curve.g2_compressed_size),
+        UseCompression::No => (parameters.curve.g1_size, parameters.curve.g2_size),
+    };
+    let public_key_position = response.len() - contribution_core::public_key_len(parameters, public_key_compression);
+    Ok(ParsedResponse {
+        challenge_hash: response[..parameters.hash_size].to_vec(),
+        body_length: public_key_position - parameters.hash_size,
+        compressed: compressed == UseCompression::Yes,
+        public_key_compressed: public_key_compression == UseCompression::Yes,
+        tau_powers_g1_length: parameters.powers_g1_length,
+        tau_powers_length: parameters.powers_length,
+        public_key: response[public_key_position..].to_vec(),
+        g1_size,
+        g2_size,
+    })
+}
+
+/// Compares the responses `a` and `b` byte by byte, and tells whether they
+/// differ before or in the public key of `parameters` at their ends. This is
+/// a debugging aid which doesn't read the responses, so they can be of any
+/// length, and a response too short for a public key is all public key.
+pub fn diff_responses<E: PairingEngine>(a: &[u8], b: &[u8], parameters: &Phase1Parameters<E>) -> ResponseDiff {
+    let first_difference = a
+        .iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())));
+    let (a_body, a_public_key) = a.split_at(a.len().saturating_sub(parameters.public_key_size));
+    let (b_body, b_public_key) = b.split_at(b.len().saturating_sub(parameters.public_key_size));
+
+    ResponseDiff {
+        first_difference,
+        a_length: a.len(),
+        b_length: b.len(),
+        bodies_match: a_body == b_body,
+        public_keys_match: a_public_key == b_public_key,
+    }
+}
+
+/// Builds the uncompressed challenge of the next contribution from a compressed
+/// `response`, as `phase1-cli` does after verifying it: the hash of the
+/// response, followed by its decompressed accumulator without the public key.
+pub fn next_challenge<E: PairingEngine + Sync>(
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+) -> Result<Vec<u8>, Phase1Error> {
+    next_challenge_with_hash(response, parameters, HashAlgorithm::default())
+}
+
+/// Builds the next challenge like `next_challenge`, for a ceremony which
+/// hashes responses with `hash_algorithm`.
+pub fn next_challenge_with_hash<E: PairingEngine + Sync>(
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    hash_algorithm: HashAlgorithm,
+) -> Result<Vec<u8>, Phase1Error> {
+    if response.len() != parameters.contribution_size {
+        return Err(Phase1Error::ResponseLength {
+            expected: parameters.contribution_size,
+            actual: response.len(),
+        });
+    }
+
+    let mut challenge = vec![0; parameters.accumulator_size];
+    challenge[..parameters.hash_size]
+        .copy_from_slice(&contribution_core::transcript_hash(&hash_algorithm.hash(response)));
+    Phase1::decompress(response, &mut challenge, CheckForCorrectness::No, parameters)
+        .map_err(|e| Phase1Error::Decompression(e.to_string()))?;
+    Ok(challenge)
+}
+
+/// Returns the first challenge of a ceremony with `parameters`, as `phase1-cli`
+/// writes it: the hash of nothing, followed by the accumulator of the
+/// untransformed generators, which is `accumulator_size` long uncompressed.
+/// Every ceremony with the same parameters starts from the same challenge.
+pub fn initial_challenge<E: PairingEngine + Sync>(parameters: &Phase1Parameters<E>) -> Result<Vec<u8>, Phase1Error> {
+    let mut challenge = vec![0; expected_challenge_len(parameters, COMPRESSED_INPUT)];
+    challenge[..parameters.hash_size].copy_from_slice(&blank_hash());
+    Phase1::initialization(&mut challenge, COMPRESSED_INPUT, parameters)
+        .map_err(|e| Phase1Error::InvalidChallenge(e.to_string()))?;
+    Ok(challenge)
+}
+
+/// Verifies `response` like `verify_response`, and returns the hash of the
+/// challenge `next_challenge` builds from it, which is what the next
+/// contribution records. An invalid response is `Phase1Error::InvalidContribution`.
+pub fn verify_response_and_next_hash<E: PairingEngine + Sync>(
+    challenge: &[u8],
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+) -> Result<Vec<u8>, Phase1Error> {
+    if !verify_response(challenge, response, parameters)? {
+        return Err(Phase1Error::InvalidContribution);
+    }
+    Ok(calculate_hash(&next_challenge(response, parameters)?).to_vec())
+}
+
+/// Verifies a chain of contributions starting from `initial_challenge`: each
+/// of `responses` must pass `verify_response` against the challenge
+/// `next_challenge` builds from the response before it. The first response
+/// which doesn't is returned as `Phase1Error::TranscriptContribution` with
+/// its index in `responses`, so a valid transcript is always `Ok(true)`.
+pub fn verify_transcript<E: PairingEngine + Sync>(
+    initial_challenge: &[u8],
+    responses: &[Vec<u8>],
+    parameters: &Phase1Parameters<E>,
+) -> Result<bool, Phase1Error> {
+    let mut challenge = initial_challenge.to_vec();
+    for (index, response) in responses.iter().enumerate() {
+        let invalid = |e: Phase1Error| Phase1Error::TranscriptContribution {
+            index,
+            reason: e.to_string(),
+        };
+        if !verify_response(&challenge, response, parameters).map_err(invalid)? {
+            return Err(invalid(Phase1Error::InvalidContribution));
+        }
+        challenge = next_challenge(response, parameters).map_err(invalid)?;
+    }
+    Ok(true)
+}
+
+/// Checks that every point of `response`, including those of the public key,
+/// is in its prime-order subgroup, skipping the pairing checks of
+/// `Phase1::verification`. A response of the wrong length is still an error.
+pub fn quick_check_response<E: PairingEngine + Sync>(
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_output: UseCompression,
+) -> Result<bool, Phase1Error> {
+    let required_output_length = contribution_core::response_len(parameters, compressed_output);
+
+    if response.len() != required_output_length {
+        return Err(Phase1Error::ResponseLength {
+            expected: required_output_length,
+            actual: response.len(),
+        });
+    }
+
+    let in_group = Phase1::deserialize(
+        response,
+        compressed_output,
+        CheckForCorrectness::OnlyInGroup,
+        parameters,
+    )
+    .is_ok()
+        && phase1::PublicKey::<E>::read(response, compressed_output, parameters).is_ok();
+    Ok(in_group)
+}
+
+/// The number of tau powers of each group `looks_trivial` reads, spread evenly
+/// over the response, so its cost doesn't grow with the size of the ceremony.
+const TRIVIAL_SAMPLES: usize = 8;
+
+/// Returns whether `response` looks like it wasn't contributed at all, as a
+/// cheap filter before a full verification: its accumulator is still the
+/// fill the response buffer is prepared with (the accumulator hash cycled,
+/// or a constant byte), or one of a sample of its tau powers is the identity,
+/// which no power of a valid contribution is. A response of the wrong length
+/// isn't flagged, since the full verification rejects it anyway.
+pub fn looks_trivial<E: PairingEngine>(
+    response: &[u8],
+    parameters: &Phase1Parameters<E>,
+    compressed_output: UseCompression,
+) -> bool {
+    if response.len() != contribution_core::response_len(parameters, compressed_output) {
+        return false;
+    }
+
+    let accumulator = &response[..response.len() - parameters.public_key_size];
+    let body = &accumulator[parameters.hash_size..];
+    let cycled_hash = body.iter().zip(accumulator.iter()).all(|(a, b)| a == b);
+    let constant = body.windows(2).all(|bytes| bytes[0] == bytes[1]);
+    if cycled_hash || constant {
+        return true;
+    }
+
+    let (g1_size, g2_size) = match compressed_output {
+        UseCompression::Yes => (parameters.curve.g1_compressed_size, parameters.curve.g2_compressed_size),
+        UseCompression::No => (parameters.curve.g1_size, parameters.curve.g2_size),
+    };
+    let tau_g2_length = match parameters.proving_system {
+        ProvingSystem::Groth16 => parameters.other_chunk_size,
+        ProvingSystem::Marlin if parameters.chunk_index == 0 => parameters.total_size_in_log2 + 2,
+        ProvingSystem::Marlin => 0,
+    };
+    let (tau_g1, rest) = body.split_at(parameters.g1_chunk_size * g1_size);
+    let tau_g2 = &rest[..tau_g2_length * g2_size];
+    samples_identity::<E::G1Affine>(tau_g1, g1_size, compressed_output)
+        || samples_identity::<E::G2Affine>(tau_g2, g2_size, compressed_output)
+}
+
+/// Returns whether one of `TRIVIAL_SAMPLES` points spread evenly over
+/// `elements`, serialized `size` bytes each, is the identity.
+fn samples_identity<G: AffineCurve>(elements: &[u8], size: usize, compression: UseCompression) -> bool {
+    let count = elements.len() / size;
+    let step = std::cmp::max(count / TRIVIAL_SAMPLES, 1);
+    (0..count).step_by(step).any(|index| {
+        let mut bytes = &elements[index * size..];
+        // Bytes which aren't points at all are left for the full verification to report.
+        matches!(
+            Deserializer::read_element::<G>(&mut bytes, compression, CheckForCorrectness::No),
+            Ok(point) if point.is_zero()
+        )
+    })
+}
+
+/// Aggregates the compressed `responses` of every chunk, in chunk order, into
+/// an uncompressed full accumulator. `parameters` are those of the first chunk.
+pub fn combine_responses<E: PairingEngine + Sync>(
+    responses: &[Vec<u8>],
+    parameters: &Phase1Parameters<E>,
+) -> Result<Vec<u8>, Phase1Error> {
+    let expected_chunks = num_chunks(
+        parameters.proving_system,
+        parameters.total_size_in_log2,
+        parameters.chunk_size,
+    )?;
+    if responses.len() != expected_chunks {
+        return Err(Phase1Error::ChunkCountMismatch {
+            expected: expected_chunks,
+            actual: responses.len(),
+        });
+    }
+
+    for (chunk_index, response) in responses.iter().enumerate() {
+        let chunk_parameters =
+            parameters.into_chunk_parameters(parameters.contribution_mode, chunk_index, parameters.chunk_size);
+        let expected_response_length = contribution_core::response_len(&chunk_parameters, COMPRESSED_OUTPUT);
+        if response.len() != expected_response_length {
+            return Err(Phase1Error::ResponseLength {
+                expected: expected_response_length,
+                actual: response.len(),
+            });
+        }
+    }
+
+    // The combined accumulator is a single chunk holding every element.
+    let parameters_for_output =
+        parameters.into_chunk_parameters(parameters.contribution_mode, 0, parameters.powers_g1_length);
+    let mut combined = vec![0; parameters_for_output.accumulator_size];
+
+    let inputs = responses
+        .iter()
+        .map(|response| (response.as_slice(), COMPRESSED_OUTPUT))
+        .collect::<Vec<_>>();
+    Phase1::aggregation(&inputs, (&mut combined, UseCompression::No), parameters)
+        .map_err(|e| Phase1Error::Aggregation(e.to_string()))?;
+
+    Ok(combined)
+}
+
+/// Splits an uncompressed full challenge into the uncompressed challenges of
+/// each chunk, in chunk order. Each chunk keeps the hash of the full challenge.
+/// `parameters` are those of the first chunk.
+pub fn split_challenge<E: PairingEngine + Sync>(
+    full_challenge: &[u8],
+    parameters: &Phase1Parameters<E>,
+) -> Result<Vec<Vec<u8>>, Phase1Error> {
+    let full_parameters =
+        parameters.into_chunk_parameters(parameters.contribution_mode, 0, parameters.powers_g1_length);
+    if full_challenge.len() != full_parameters.accumulator_size {
+        return Err(Phase1Error::ChallengeLength {
+            expected: full_parameters.accumulator_size,
+            actual: full_challenge.len(),
+        });
+    }
+
+    let full = Phase1::deserialize(
+        full_challenge,
+        UseCompression::No,
+        CheckForCorrectness::No,
+        &full_parameters,
+    )
+    .map_err(|e| Phase1Error::InvalidChallenge(e.to_string()))?;
+
+    let num_chunks = num_chunks(
+        parameters.proving_system,
+        parameters.total_size_in_log2,
+        parameters.chunk_size,
+    )?;
+    let mut chunks = Vec::with_capacity(num_chunks);
+    for chunk_index in 0..num_chunks {
+        let chunk_parameters =
+            parameters.into_chunk_parameters(parameters.contribution_mode, chunk_index, parameters.chunk_size);
+
+        let start = chunk_index * parameters.chunk_size;
+        let tau_g1 = start..start + chunk_parameters.g1_chunk_size;
+        let chunk = match parameters.proving_system {
+            ProvingSystem::Groth16 => {
+                let other = start..start + chunk_parameters.other_chunk_size;
+                Phase1 {
+                    tau_powers_g1: full.tau_powers_g1[tau_g1].to_vec(),
+                    tau_powers_g2: full.tau_powers_g2[other.clone()].to_vec(),
+                    alpha_tau_powers_g1: full.alpha_tau_powers_g1[other.clone()].to_vec(),
+                    beta_tau_powers_g1: full.beta_tau_powers_g1[other].to_vec(),
+                    beta_g2: full.beta_g2,
+                    hash: full.hash,
+                    parameters: &chunk_parameters,
+                }
+            }
+            // The extra Marlin elements all live in the first chunk.
+            ProvingSystem::Marlin => Phase1 {
+                tau_powers_g1: full.tau_powers_g1[tau_g1].to_vec(),
+                tau_powers_g2: match chunk_index {
+                    0 => full.tau_powers_g2.clone(),
+                    _ => vec![],
+                },
+                alpha_tau_powers_g1: match chunk_index {
+                    0 => full.alpha_tau_powers_g1.clone(),
+                    _ => vec![],
+                },
+                beta_tau_powers_g1: vec![],
+                beta_g2: full.beta_g2,
+                hash: full.hash,
+                parameters: &chunk_parameters,
+            },
+        };
+
+        let mut chunk_challenge = vec![0; chunk_parameters.accumulator_size];
+        chunk_challenge[0..parameters.hash_size].copy_from_slice(&full_challenge[0..parameters.hash_size]);
+        chunk
+            .serialize(&mut chunk_challenge, UseCompression::No, &chunk_parameters)
+            .map_err(|e| Phase1Error::InvalidChallenge(format!("chunk {} can't be serialized: {}", chunk_index, e)))?;
+        chunks.push(chunk_challenge);
+    }
+
+    Ok(chunks)
+}
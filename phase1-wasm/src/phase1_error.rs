@@ -0,0 +1,154 @@
+//! The errors of contributing and verifying, without `wasm_bindgen`, so
+//! `contribution_core` can return them where it isn't available. `errors`
+//! converts them into the values handed to JS.
+
+use thiserror::Error;
+
+/// Errors that might occur while contributing to or verifying a Phase 1 challenge.
+#[derive(Debug, Error)]
+pub enum Phase1Error {
+    #[error("The size of challenge file should be {expected}, but it's {actual}, so something isn't right.")]
+    ChallengeLength { expected: usize, actual: usize },
+    #[error("The size of response file should be {expected}, but it's {actual}, so something isn't right.")]
+    ResponseLength { expected: usize, actual: usize },
+    #[error("The chunk index should be less than {num_chunks}, but it's {index}, so something isn't right.")]
+    ChunkIndexOutOfRange { index: usize, num_chunks: usize },
+    #[error("could not generate keypair")]
+    KeyGeneration,
+    #[error("must contribute with the key: {0}")]
+    Computation(String),
+    #[error("could not write the public key: {0}")]
+    PublicKeyWrite(String),
+    #[error("could not read the public key: {0}")]
+    PublicKeyRead(String),
+    #[error("the contribution is invalid")]
+    InvalidContribution,
+    #[error("unsupported curve: {0}")]
+    InvalidCurve(String),
+    #[error("unsupported proving system: {0}")]
+    InvalidProvingSystem(String),
+    #[error("the contribution was cancelled")]
+    Cancelled,
+    #[error("the challenge is invalid: {0}")]
+    InvalidChallenge(String),
+    #[error("The seed should be at least 32 bytes, but it's {len}, so something isn't right.")]
+    SeedTooShort { len: usize },
+    #[error("The number of chunks should be {expected}, but it's {actual}, so something isn't right.")]
+    ChunkCountMismatch { expected: usize, actual: usize },
+    #[error("could not combine the chunks: {0}")]
+    Aggregation(String),
+    #[error("could not run the worker pool: {0}")]
+    WorkerPool(String),
+    #[error("the contribution ended without returning a result")]
+    Channel,
+    #[error("could not stream the response: {0}")]
+    Sink(String),
+    #[error("the public key written to the response does not read back the same")]
+    PublicKeySelfCheck,
+    #[error("could not use the checkpoint: {0}")]
+    Checkpoint(String),
+    #[error("the ceremony parameters are invalid: {0}")]
+    InvalidParameters(String),
+    #[error("could not access a file: {0}")]
+    Io(String),
+    #[error("could not decompress the response: {0}")]
+    Decompression(String),
+    #[error("contribution {index} of the transcript is invalid: {reason}")]
+    TranscriptContribution { index: usize, reason: String },
+    #[error("could not compress or decompress the response for transport: {0}")]
+    TransportCompression(String),
+    #[error("the prior contribution is invalid, so it was not contributed to")]
+    PriorContributionInvalid,
+    #[error("the response is malformed: {0}")]
+    Malformed(String),
+    #[error("The power should be at most {max}, but it's {requested}, so the contribution wouldn't fit in memory.")]
+    PowerTooLarge { requested: usize, max: usize },
+    #[error("the response format is unsupported: {0}")]
+    UnsupportedFormat(String),
+    #[error("the keypair is not a keypair for the challenge it is contributed to")]
+    KeypairMismatch,
+    #[error("could not reserve the memory of the contribution: {0}")]
+    MemoryReservation(String),
+    #[error("the curve {0} isn't compiled into this build, so its feature must be enabled")]
+    CurveNotCompiled(String),
+    #[error("could not gather the entropy of the contribution: {0}")]
+    Entropy(String),
+    #[error("could not encode or decode the response: {0}")]
+    Encoding(String),
+    #[error(
+        "The size of challenge file should be {expected}, but it's {actual}, the size of {} challenge, so the challenge compression is likely misconfigured.",
+        compression_name(.compressed)
+    )]
+    WrongCompression {
+        expected: usize,
+        actual: usize,
+        compressed: bool,
+    },
+    #[error("the challenge is empty, so it was not contributed to")]
+    EmptyChallenge,
+    #[error("could not download the challenge: {0}")]
+    Fetch(String),
+    #[error("could not download the challenge, as the server responded with status {status}")]
+    FetchStatus { status: u16 },
+    #[error("The Content-Length of the challenge is {expected}, but {actual} bytes were received, so the download is incomplete.")]
+    DownloadLength { expected: usize, actual: usize },
+    #[error("could not read the clock: {0}")]
+    Clock(String),
+}
+
+/// Names the compression of a challenge in the message of `Phase1Error::WrongCompression`.
+fn compression_name(compressed: &bool) -> &'static str {
+    if *compressed {
+        "a compressed"
+    } else {
+        "an uncompressed"
+    }
+}
+
+impl Phase1Error {
+    /// Returns a stable discriminant for the kind of error, which JS callers can switch on.
+    pub fn code(&self) -> u32 {
+        match self {
+            Phase1Error::ChallengeLength { .. } => 0,
+            Phase1Error::ResponseLength { .. } => 1,
+            Phase1Error::ChunkIndexOutOfRange { .. } => 2,
+            Phase1Error::KeyGeneration => 3,
+            Phase1Error::Computation(_) => 4,
+            Phase1Error::PublicKeyWrite(_) => 5,
+            Phase1Error::PublicKeyRead(_) => 6,
+            Phase1Error::InvalidContribution => 7,
+            Phase1Error::InvalidCurve(_) => 8,
+            Phase1Error::InvalidProvingSystem(_) => 9,
+            Phase1Error::Cancelled => 10,
+            Phase1Error::InvalidChallenge(_) => 11,
+            Phase1Error::SeedTooShort { .. } => 12,
+            Phase1Error::ChunkCountMismatch { .. } => 13,
+            Phase1Error::Aggregation(_) => 14,
+            Phase1Error::WorkerPool(_) => 15,
+            Phase1Error::Channel => 16,
+            Phase1Error::Sink(_) => 17,
+            Phase1Error::PublicKeySelfCheck => 18,
+            Phase1Error::Checkpoint(_) => 19,
+            Phase1Error::InvalidParameters(_) => 20,
+            Phase1Error::Io(_) => 21,
+            Phase1Error::Decompression(_) => 22,
+            Phase1Error::TranscriptContribution { .. } => 23,
+            Phase1Error::TransportCompression(_) => 24,
+            Phase1Error::PriorContributionInvalid => 25,
+            Phase1Error::Malformed(_) => 26,
+            Phase1Error::PowerTooLarge { .. } => 27,
+            Phase1Error::UnsupportedFormat(_) => 28,
+            Phase1Error::KeypairMismatch => 29,
+            Phase1Error::MemoryReservation(_) => 30,
+            Phase1Error::CurveNotCompiled(_) => 31,
+            Phase1Error::Entropy(_) => 32,
+            Phase1Error::Encoding(_) => 33,
+            Phase1Error::WrongCompression { .. } => 34,
+            Phase1Error::EmptyChallenge => 35,
+            Phase1Error::Fetch(_) => 36,
+            Phase1Error::FetchStatus { .. } => 37,
+            Phase1Error::DownloadLength { .. } => 38,
+            Phase1Error::Clock(_) => 39,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::Phase1Error;
+
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// The tokens left to a participant, as of the time they were last counted.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_ms: f64,
+}
+
+impl Bucket {
+    /// The tokens in the bucket at `now`, refilled at `capacity` per `window_ms`.
+    fn refilled(&self, now: f64, capacity: f64, window_ms: f64) -> f64 {
+        // A clock which goes backwards refills nothing rather than draining the bucket.
+        let elapsed_ms = (now - self.updated_ms).max(0.0);
+        (self.tokens + elapsed_ms * capacity / window_ms).min(capacity)
+    }
+}
+
+/// Throttles how often each participant of a coordinator can submit
+/// contributions, with a token bucket per participant id. A participant can
+/// submit up to `capacity` contributions at once, and the bucket refills
+/// evenly over `window_ms`, so after a burst it is full again `window_ms` later.
+///
+/// The ids are opaque to the limiter, which only compares them. Time comes
+/// from a clock returning milliseconds, which is the wall clock by default.
+///
+/// A full bucket is the same as none, so the limiter only keeps the buckets of
+/// participants who submitted within the last window, and drops the others
+/// once per window.
+#[wasm_bindgen]
+pub struct RateLimiter {
+    capacity: u32,
+    window_ms: f64,
+    buckets: HashMap<String, Bucket>,
+    swept_ms: f64,
+    clock: Box<dyn Fn() -> Result<f64, Phase1Error>>,
+}
+
+#[wasm_bindgen]
+impl RateLimiter {
+    /// A limiter allowing `capacity` submissions per participant every `window_ms`, on the wall clock.
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: u32, window_ms: f64) -> Result<RateLimiter, Phase1Error> {
+        Self::with_clock(capacity, window_ms, now_ms)
+    }
+
+    /// A limiter like `new`, with time read from `clock`, which is called
+    /// without arguments and must return milliseconds as a number. A clock
+    /// which throws or returns anything else fails `try_acquire` with
+    /// `Phase1Error::Clock`.
+    pub fn with_clock_function(
+        capacity: u32,
+        window_ms: f64,
+        clock: js_sys::Function,
+    ) -> Result<RateLimiter, Phase1Error> {
+        Self::with_fallible_clock(capacity, window_ms, move || {
+            let now = clock
+                .call0(&JsValue::NULL)
+                .map_err(|e| Phase1Error::Clock(format!("{:?}", e)))?;
+            now.as_f64()
+                .ok_or_else(|| Phase1Error::Clock(format!("{:?} isn't a number of milliseconds", now)))
+        })
+    }
+
+    /// Takes a token from the bucket of the participant `id`, returning whether
+    /// there was one, so whether the participant may submit now.
+    pub fn try_acquire(&mut self, id: &str) -> Result<bool, Phase1Error> {
+        let now = (self.clock)()?;
+        let (capacity, window_ms) = (f64::from(self.capacity), self.window_ms);
+        self.sweep(now);
+
+        let bucket = self.buckets.entry(id.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_ms: now,
+        });
+        bucket.tokens = bucket.refilled(now, capacity, window_ms);
+        bucket.updated_ms = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// The number of participants the limiter keeps a bucket for.
+    #[wasm_bindgen(getter)]
+    pub fn tracked_participants(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+impl RateLimiter {
+    /// A limiter like `new`, with time read from `clock`, in milliseconds.
+    pub fn with_clock(
+        capacity: u32,
+        window_ms: f64,
+        clock: impl Fn() -> f64 + 'static,
+    ) -> Result<RateLimiter, Phase1Error> {
+        Self::with_fallible_clock(capacity, window_ms, move || Ok(clock()))
+    }
+
+    /// A limiter like `with_clock`, with a clock which can fail to tell the time.
+    pub(crate) fn with_fallible_clock(
+        capacity: u32,
+        window_ms: f64,
+        clock: impl Fn() -> Result<f64, Phase1Error> + 'static,
+    ) -> Result<RateLimiter, Phase1Error> {
+        if capacity == 0 {
+            return Err(Phase1Error::InvalidParameters(
+                "the rate limit must allow at least one submission".to_string(),
+            ));
+        }
+        if window_ms.is_nan() || window_ms <= 0.0 {
+            return Err(Phase1Error::InvalidParameters(format!(
+                "the rate limit window should be positive, but it's {}",
+                window_ms
+            )));
+        }
+
+        Ok(RateLimiter {
+            capacity,
+            window_ms,
+            buckets: HashMap::new(),
+            swept_ms: f64::NEG_INFINITY,
+            clock: Box::new(clock),
+        })
+    }
+
+    /// Drops the buckets which have refilled to capacity by `now`, at most
+    /// once per window, so a call doesn't go over every bucket.
+    fn sweep(&mut self, now: f64) {
+        if now - self.swept_ms < self.window_ms {
+            return;
+        }
+        self.swept_ms = now;
+        let (capacity, window_ms) = (f64::from(self.capacity), self.window_ms);
+        self.buckets
+            .retain(|_, bucket| bucket.refilled(now, capacity, window_ms) < capacity);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
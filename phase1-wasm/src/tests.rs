@@ -1,11 +1,19 @@
-use crate::phase1::*;
-use phase1::{ContributionMode, Phase1, Phase1Parameters, ProvingSystem};
-use setup_utils::{batch_exp, blank_hash, generate_powers_of_tau, UseCompression};
+use crate::{
+    attestation::*,
+    checkpoint::*,
+    contribution_core::{self, fill_response},
+    errors::{Phase1Error, Phase1ErrorValue},
+    phase1::*,
+    rate_limiter::*,
+};
+use phase1::{helpers::proving_system_from_str, ContributionMode, Phase1, Phase1Parameters, ProvingSystem};
+use setup_utils::{batch_exp, blank_hash, calculate_hash, generate_powers_of_tau, CheckForCorrectness, UseCompression};
 use snarkvm_curves::{bls12_377::Bls12_377, bw6_761::BW6_761, PairingEngine};
 use snarkvm_fields::{batch_inversion, Field};
 
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
+use sha2::{Digest, Sha256};
 use std::ops::Mul;
 use wasm_bindgen_test::*;
 
@@ -199,3 +207,2625 @@ fn test_phase1_contribute_bw6_761_chunked() {
         }
     }
 }
+
+fn verify_response_test<E: PairingEngine + Sync>(parameters: &Phase1Parameters<E>) {
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let response = contribute_challenge(&challenge, parameters, ChaChaRng::seed_from_u64(0))
+        .unwrap()
+        .response;
+
+    assert!(verify_response(&challenge, &response, &parameters).unwrap());
+
+    // The response must not verify against a challenge it wasn't based on.
+    let mut other_challenge = challenge.clone();
+    other_challenge[0] ^= 1;
+    assert!(!verify_response(&other_challenge, &response, &parameters).unwrap());
+
+    // A truncated response is rejected outright.
+    assert!(verify_response(&challenge, &response[1..], &parameters).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_bls12_377_full() {
+    for proving_system in &[ProvingSystem::Groth16, ProvingSystem::Marlin] {
+        verify_response_test(&get_parameters_full::<Bls12_377>(*proving_system, 2, 2));
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_bls12_377_chunked() {
+    for proving_system in &[ProvingSystem::Groth16, ProvingSystem::Marlin] {
+        let powers = 4;
+        let chunk_size = 3 + 3 * powers + 1; // to ensure the Marlin extra elements fit in chunk 0
+        for i in 0..num_chunks(*proving_system, powers, chunk_size).unwrap() {
+            verify_response_test(&get_parameters_chunked::<Bls12_377>(
+                *proving_system,
+                powers,
+                2,
+                i,
+                chunk_size,
+            ));
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_wrong_challenge_length() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    match contribute_challenge(&challenge[1..], &parameters, ChaChaRng::seed_from_u64(0)) {
+        Err(Phase1Error::ChallengeLength { expected, actual }) => {
+            assert_eq!(expected, challenge.len());
+            assert_eq!(actual, challenge.len() - 1);
+        }
+        _ => panic!("expected a challenge length error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_contribute_full_bls12_377() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let result = Phase1WASM::contribute_full("bls12_377", "groth16", 2, 2, challenge.clone(), None, None, None).unwrap();
+    assert_eq!(result.response().len(), parameters.contribution_size);
+    assert!(Phase1WASM::verify_contribution("bls12_377", "groth16", 2, 2, &challenge, &result.response(), None).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_getters() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result.current_accumulator_hash(), calculate_hash(&challenge).as_slice());
+    assert_eq!(result.contribution_hash(), calculate_hash(&result.response()).as_slice());
+
+    let public_key_position = result.response.len() - parameters.public_key_size;
+    assert_eq!(result.public_key(), &result.response[public_key_position..]);
+    assert!(phase1::PublicKey::<Bls12_377>::read(&result.response, COMPRESSED_OUTPUT, &parameters).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_progress() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut fractions = vec![];
+    let mut progress = |fraction: f64| fractions.push(fraction);
+    let options = ContributionOptions {
+        progress: Some(&mut progress),
+        ..Default::default()
+    };
+    let result =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+
+    // Progress is reported in order and finishes at 1.0, without changing the response.
+    assert!(fractions.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(fractions.last(), Some(&1.0));
+    let expected = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result.response, expected.response);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_cancelled() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    // Cancelling part-way through the response, as JS would from a button click.
+    let cancel = CancelToken::new();
+    let handle = cancel.handle();
+    let mut progress = |fraction: f64| {
+        if fraction > 0.0 {
+            cancel.cancel();
+        }
+    };
+    let options = ContributionOptions {
+        progress: Some(&mut progress),
+        cancel: Some(&handle),
+        ..Default::default()
+    };
+    match contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options) {
+        Err(Phase1Error::Cancelled) => {}
+        _ => panic!("expected the contribution to be cancelled"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_compressed_input() {
+    let serialization = SerializationOptions {
+        compressed_input: UseCompression::Yes,
+        compressed_output: UseCompression::Yes,
+        check_input: CHECK_INPUT_CORRECTNESS,
+    };
+    for proving_system in &[ProvingSystem::Groth16, ProvingSystem::Marlin] {
+        let parameters = get_parameters_full::<Bls12_377>(*proving_system, 2, 2);
+        let (challenge, _) = generate_input(&parameters, UseCompression::Yes);
+
+        let options = ContributionOptions {
+            serialization,
+            ..Default::default()
+        };
+        let result =
+            contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+        assert_eq!(result.response.len(), parameters.contribution_size);
+        assert!(verify_response_with_options(&challenge, &result.response, &parameters, serialization).unwrap());
+
+        // A compressed challenge is rejected under the default options.
+        match contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)) {
+            Err(Phase1Error::WrongCompression { compressed: true, .. }) => {}
+            _ => panic!("expected a challenge compression error"),
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_tampered_challenge() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (mut challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    // Corrupt the first tau_g1 element, just after the hash.
+    challenge[parameters.hash_size + 1] ^= 0xff;
+
+    let options = ContributionOptions {
+        serialization: SerializationOptions {
+            check_input: CheckForCorrectness::Full,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    match contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options) {
+        Err(Phase1Error::InvalidChallenge(_)) => {}
+        _ => panic!("expected an invalid challenge error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_contribute_full_with_seed() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let seed = [7u8; 32];
+
+    let first = Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, challenge.clone(), &seed).unwrap();
+    let second = Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, challenge.clone(), &seed).unwrap();
+    assert_eq!(first.response, second.response);
+    assert!(verify_response(&challenge, &first.response, &parameters).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_contribute_short_seed() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    match Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, challenge, &[7u8; 16]) {
+        Err(Phase1Error::SeedTooShort { len }) => assert_eq!(len, 16),
+        _ => panic!("expected a short seed error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_dispatch_curve() {
+    use phase1::helpers::CurveKind;
+
+    let sizes = |curve_kind: CurveKind| -> Result<ParameterSizes, Phase1Error> {
+        Ok(dispatch_curve!(curve_kind, |E| {
+            let parameters = get_parameters_full::<E>(ProvingSystem::Groth16, 2, 2);
+            ParameterSizes::from(&parameters)
+        }))
+    };
+    assert_eq!(
+        sizes(CurveKind::Bls12_377).unwrap(),
+        ParameterSizes::from(&get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2))
+    );
+    assert_eq!(
+        sizes(CurveKind::BW6).unwrap(),
+        ParameterSizes::from(&get_parameters_full::<BW6_761>(ProvingSystem::Groth16, 2, 2))
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_parameter_sizes() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let sizes = Phase1WASM::parameter_sizes("bls12_377", "groth16", 2, 2, None, None).unwrap();
+    assert_eq!(sizes, ParameterSizes::from(&parameters));
+    assert_eq!(sizes.accumulator_size(), parameters.accumulator_size);
+    assert_eq!(sizes.contribution_size(), parameters.contribution_size);
+    assert_eq!(sizes.public_key_size(), parameters.public_key_size);
+
+    let parameters = get_parameters_chunked::<BW6_761>(ProvingSystem::Groth16, 4, 2, 1, 8);
+    let sizes = Phase1WASM::parameter_sizes("bw6", "groth16", 2, 4, Some(1), Some(8)).unwrap();
+    assert_eq!(sizes, ParameterSizes::from(&parameters));
+
+    match Phase1WASM::parameter_sizes("bw6", "groth16", 2, 4, Some(4), Some(8)) {
+        Err(Phase1Error::ChunkIndexOutOfRange { index, num_chunks }) => assert_eq!((index, num_chunks), (4, 4)),
+        _ => panic!("expected a chunk index error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_combine_chunks() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let num_chunks = num_chunks(ProvingSystem::Groth16, power, chunk_size).unwrap();
+    assert_eq!(num_chunks, 2);
+
+    let mut responses = vec![];
+    let mut tau_powers_g1 = vec![];
+    for chunk_index in 0..num_chunks {
+        let parameters =
+            get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size);
+        let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+        let response = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0))
+            .unwrap()
+            .response;
+
+        let deserialized =
+            Phase1::deserialize(&response, COMPRESSED_OUTPUT, CHECK_INPUT_CORRECTNESS, &parameters).unwrap();
+        tau_powers_g1.extend(deserialized.tau_powers_g1);
+        responses.push(response);
+    }
+
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, 0, chunk_size);
+    let combined = combine_responses(&responses, &parameters).unwrap();
+
+    let full_parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, batch_size);
+    assert_eq!(combined.len(), full_parameters.accumulator_size);
+    let deserialized =
+        Phase1::deserialize(&combined, UseCompression::No, CHECK_INPUT_CORRECTNESS, &full_parameters).unwrap();
+    assert_eq!(deserialized.tau_powers_g1, tau_powers_g1);
+
+    match combine_responses(&responses[1..], &parameters) {
+        Err(Phase1Error::ChunkCountMismatch { expected, actual }) => assert_eq!((expected, actual), (2, 1)),
+        _ => panic!("expected a chunk count error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_split_contribute_combine() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let full_parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, batch_size);
+    let (full_challenge, _) = generate_input(&full_parameters, COMPRESSED_INPUT);
+
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, 0, chunk_size);
+    let chunks = split_challenge(&full_challenge, &parameters).unwrap();
+    assert_eq!(
+        chunks.len(),
+        num_chunks(ProvingSystem::Groth16, power, chunk_size).unwrap()
+    );
+
+    let mut responses = vec![];
+    for (chunk_index, chunk) in chunks.iter().enumerate() {
+        let chunk_parameters =
+            get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size);
+        // The initial full challenge splits into the initial chunk challenges.
+        assert_eq!(chunk, &generate_input(&chunk_parameters, COMPRESSED_INPUT).0);
+
+        let result = contribute_challenge(chunk, &chunk_parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+        assert!(verify_response(chunk, &result.response, &chunk_parameters).unwrap());
+        responses.push(result.response);
+    }
+
+    let combined = combine_responses(&responses, &parameters).unwrap();
+    assert!(Phase1::deserialize(
+        &combined,
+        UseCompression::No,
+        CheckForCorrectness::Full,
+        &full_parameters
+    )
+    .is_ok());
+
+    match split_challenge(&full_challenge[1..], &parameters) {
+        Err(Phase1Error::ChallengeLength { .. }) => {}
+        _ => panic!("expected a challenge length error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_fill_response() {
+    let hash = calculate_hash(b"challenge");
+    for length in &[0, 1, 63, 64, 65, 200, 1000] {
+        // The original byte-by-byte fill.
+        let expected: Vec<u8> = (0..*length).map(|i| hash[i % hash.len()]).collect();
+
+        let mut response = vec![1, 2, 3];
+        fill_response(&mut response, &hash, *length, ResponseFill::AccumulatorHash);
+        assert_eq!(response, expected);
+
+        let header_length = std::cmp::min(hash.len(), *length);
+        for (fill, byte) in &[(ResponseFill::Zero, 0), (ResponseFill::Pattern(0xab), 0xab)] {
+            fill_response(&mut response, &hash, *length, *fill);
+            assert_eq!(response.len(), *length);
+            assert_eq!(response[..header_length], hash[..header_length]);
+            assert!(response[header_length..].iter().all(|b| b == byte));
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_response_fill_does_not_change_response() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let contribute = |fill| {
+        let options = ContributionOptions {
+            fill,
+            ..Default::default()
+        };
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options)
+            .unwrap()
+            .response
+    };
+    let expected = contribute(ResponseFill::AccumulatorHash);
+    assert_eq!(contribute(ResponseFill::Zero), expected);
+    assert_eq!(contribute(ResponseFill::Pattern(0xab)), expected);
+    assert!(verify_response(&challenge, &expected, &parameters).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_into_reused_buffer() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut response = vec![];
+    for seed in 0..2 {
+        let expected = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(seed)).unwrap();
+        let result =
+            contribute_challenge_into(&challenge, &parameters, ChaChaRng::seed_from_u64(seed), &mut response).unwrap();
+        assert_eq!(response, expected.response);
+        assert_eq!(result.contribution_hash(), expected.contribution_hash());
+        assert_eq!(result.public_key(), expected.public_key());
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_challenge_hash() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(
+        Phase1WASM::challenge_hash(&challenge),
+        result.current_accumulator_hash()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_contribute_beacon() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let beacon_hash = [3u8; 32];
+
+    let result =
+        Phase1WASM::contribute_beacon("bls12_377", "groth16", 2, 2, challenge.clone(), &beacon_hash, 16).unwrap();
+    let seed = result.beacon_seed().unwrap();
+    assert_eq!(seed, beacon_seed(&beacon_hash, 16));
+    assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+
+    // Anyone can reproduce the contribution from the beacon.
+    let reproduced = Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, challenge, &seed).unwrap();
+    assert_eq!(result.response, reproduced.response);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_pool_sequential_chunks() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let pool = Phase1Pool::new_native(2).unwrap();
+
+    for chunk_index in 0..num_chunks(ProvingSystem::Groth16, power, chunk_size).unwrap() {
+        let parameters =
+            get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size);
+        let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+        let result = pool
+            .contribute_chunk(
+                "bls12_377",
+                "groth16",
+                batch_size,
+                power,
+                chunk_index,
+                chunk_size,
+                &[0u8; 32],
+                challenge.clone(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_pool_zero_threads() {
+    match Phase1Pool::new_native(0) {
+        Err(Phase1Error::WorkerPool(_)) => {}
+        _ => panic!("expected a worker pool error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_streaming() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut streamed = vec![];
+    let result = contribute_challenge_streaming(&challenge, &parameters, ChaChaRng::seed_from_u64(0), |slice| {
+        streamed.extend_from_slice(slice);
+        Ok(())
+    })
+    .unwrap();
+
+    let expected = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(streamed, expected.response);
+    assert_eq!(result.contribution_hash(), expected.contribution_hash());
+    assert!(result.response.is_empty());
+
+    match contribute_challenge_streaming(&challenge, &parameters, ChaChaRng::seed_from_u64(0), |_| {
+        Err("upload failed".to_string())
+    }) {
+        Err(Phase1Error::Sink(message)) => assert_eq!(message, "upload failed"),
+        _ => panic!("expected a sink error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_estimate_memory() {
+    for proving_system in &["groth16", "marlin"] {
+        let parameters = get_parameters_full::<Bls12_377>(proving_system_from_str(proving_system).unwrap(), 3, 4);
+        let estimate = Phase1WASM::estimate_memory("bls12_377", proving_system, 3, 4).unwrap();
+        assert_eq!(estimate, estimate_contribution_memory(&parameters));
+        assert!(estimate > parameters.accumulator_size + parameters.contribution_size);
+        assert!(Phase1WASM::estimate_memory("bls12_377", proving_system, 4, 4).unwrap() > estimate);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_strict() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    // The self-check passes on a well-formed response and doesn't change it.
+    let strict = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let options = ContributionOptions {
+        strict: false,
+        ..Default::default()
+    };
+    let relaxed =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+    assert_eq!(strict.response, relaxed.response);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_validate_challenge() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let checked = SerializationOptions::new(false, true, true);
+
+    assert!(
+        Phase1WASM::validate_challenge("bls12_377", "groth16", 2, 2, &challenge, None, None, Some(checked)).is_ok()
+    );
+    match Phase1WASM::validate_challenge("bls12_377", "groth16", 2, 2, &challenge[1..], None, None, None) {
+        Err(Phase1Error::ChallengeLength { expected, actual }) => {
+            assert_eq!((expected, actual), (challenge.len(), challenge.len() - 1))
+        }
+        _ => panic!("expected a challenge length error"),
+    }
+
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, 2, 2, 1, 4);
+    let (chunk, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    assert!(
+        Phase1WASM::validate_challenge("bls12_377", "groth16", 2, 2, &chunk, Some(1), Some(4), Some(checked)).is_ok()
+    );
+    // The full challenge is the wrong size for a chunk.
+    assert!(Phase1WASM::validate_challenge("bls12_377", "groth16", 2, 2, &challenge, Some(1), Some(4), None).is_err());
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen_test]
+fn test_phase1_init_hooks_once() {
+    // Another test may have installed the hooks already, so only the second call is known.
+    init_hooks();
+    assert!(!init_hooks());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_native_contributor() {
+    use crate::native::NativeContributor;
+    use phase1::helpers::CurveKind;
+
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let contributor = NativeContributor::new(CurveKind::Bls12_377, ProvingSystem::Groth16, power, batch_size);
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, batch_size);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contributor
+        .contribute_full(&challenge, ChaChaRng::seed_from_u64(0))
+        .unwrap();
+    assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+
+    for chunk_index in 0..num_chunks(ProvingSystem::Groth16, power, chunk_size).unwrap() {
+        let parameters =
+            get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size);
+        let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+        let result = contributor
+            .contribute_chunked(chunk_index, chunk_size, &challenge, ChaChaRng::seed_from_u64(0))
+            .unwrap();
+        assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_native_verifier_cache() {
+    use crate::native::NativeVerifier;
+    use phase1::helpers::CurveKind;
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let verifier = NativeVerifier::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 2, 2).cache(4);
+    assert!(verifier.verify(&challenge, &result.response).unwrap());
+    assert_eq!(verifier.cache_hits(), 0);
+
+    // Copies of the same bytes hit the cache.
+    let (challenge_copy, response_copy) = (challenge.clone(), result.response.clone());
+    assert!(verifier.verify(&challenge_copy, &response_copy).unwrap());
+    assert_eq!(verifier.cache_hits(), 1);
+
+    let mut corrupted = result.response.clone();
+    corrupted[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    assert!(!verifier.verify(&challenge, &corrupted).unwrap());
+    assert!(!verifier.verify(&challenge, &corrupted).unwrap());
+    assert_eq!(verifier.cache_hits(), 2);
+
+    let uncached = NativeVerifier::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 2, 2);
+    assert!(uncached.verify(&challenge, &result.response).unwrap());
+    assert!(uncached.verify(&challenge, &result.response).unwrap());
+    assert_eq!(uncached.cache_hits(), 0);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_native_streaming_verifier() {
+    use crate::native::NativeVerifier;
+    use phase1::helpers::CurveKind;
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let mut corrupted = result.response.clone();
+    corrupted[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    let mut unlinked = result.response.clone();
+    unlinked[0] ^= 0xff;
+
+    let verifier = NativeVerifier::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 2, 2);
+    let stream = |response: &[u8], piece_length: usize| {
+        let mut streaming = verifier.streaming(&challenge).unwrap();
+        for piece in response.chunks(piece_length) {
+            streaming.push(piece);
+        }
+        streaming.finish()
+    };
+    for response in [&result.response, &corrupted, &unlinked] {
+        let one_shot = verifier.verify(&challenge, response).unwrap();
+        for piece_length in [1, 7, parameters.hash_size, response.len()] {
+            assert_eq!(stream(&response[..], piece_length).unwrap(), one_shot);
+        }
+    }
+    assert!(stream(&result.response[..], 7).unwrap());
+
+    let mut too_long = result.response.clone();
+    too_long.push(0);
+    for response in [&result.response[1..], &too_long[..]] {
+        match stream(response, 7) {
+            Err(Phase1Error::ResponseLength { expected, actual }) => {
+                assert_eq!((expected, actual), (result.response.len(), response.len()))
+            }
+            _ => panic!("expected a response length error"),
+        }
+    }
+    assert!(matches!(
+        verifier.streaming(&challenge[1..]),
+        Err(Phase1Error::ChallengeLength { .. })
+    ));
+
+    // The streamed hash keys the cache like the one-shot hash.
+    let cached = NativeVerifier::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 2, 2).cache(4);
+    assert!(cached.verify(&challenge, &result.response).unwrap());
+    let mut streaming = cached.streaming(&challenge).unwrap();
+    streaming.push(&result.response);
+    assert!(streaming.finish().unwrap());
+    assert_eq!(cached.cache_hits(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_timing() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let untimed = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert!(untimed.timing().is_none());
+
+    let options = ContributionOptions {
+        timing: true,
+        ..Default::default()
+    };
+    let timed =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+    let timing = timed.timing().unwrap();
+    for ms in [
+        timing.key_generation_ms,
+        timing.computation_ms,
+        timing.write_ms,
+        timing.hash_ms,
+    ] {
+        assert!(ms >= 0.0);
+    }
+    assert_eq!(untimed.response, timed.response);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_precomputed_hash() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let computed = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let hash = calculate_hash(&challenge);
+    let options = ContributionOptions {
+        current_accumulator_hash: Some(&hash),
+        ..Default::default()
+    };
+    let provided =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+
+    assert_eq!(computed.response, provided.response);
+    assert_eq!(computed.current_accumulator_hash(), provided.current_accumulator_hash());
+
+    // The hash must have the length of the digests of the hash algorithm.
+    let options = ContributionOptions {
+        current_accumulator_hash: Some(&hash),
+        hash_algorithm: HashAlgorithm::Sha256,
+        ..Default::default()
+    };
+    assert!(matches!(
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_valid_names_round_trip() {
+    for curve in CURVES {
+        assert!(phase1::helpers::curve_from_str(curve).is_ok());
+    }
+    for proving_system in PROVING_SYSTEMS {
+        assert!(proving_system_from_str(proving_system).is_ok());
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn test_phase1_wasm_valid_names() {
+    let curves: Vec<String> = valid_curves().iter().filter_map(|curve| curve.as_string()).collect();
+    assert_eq!(curves, CURVES);
+    let proving_systems: Vec<String> = valid_proving_systems()
+        .iter()
+        .filter_map(|proving_system| proving_system.as_string())
+        .collect();
+    assert_eq!(proving_systems, PROVING_SYSTEMS);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_invalid_names() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    match Phase1WASM::contribute_full_with_seed("bls12_999", "groth16", 2, 2, challenge.clone(), &[0u8; 32]) {
+        Err(Phase1Error::InvalidCurve(_)) => {}
+        _ => panic!("expected an invalid curve error"),
+    }
+    match Phase1WASM::contribute_full_with_seed("bls12_377", "plonk", 2, 2, challenge, &[0u8; 32]) {
+        Err(Phase1Error::InvalidProvingSystem(_)) => {}
+        _ => panic!("expected an invalid proving system error"),
+    }
+    assert!(matches!(
+        Phase1WASM::estimate_memory("bls12_999", "groth16", 2, 2),
+        Err(Phase1Error::InvalidCurve(_))
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_pool_invalid_names() {
+    let pool = Phase1Pool::new_native(1).unwrap();
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, 2, 2, 0, 4);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let result = pool.contribute_chunk(
+        "bls12_999",
+        "groth16",
+        2,
+        2,
+        0,
+        4,
+        &[0u8; 32],
+        challenge,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert!(matches!(result, Err(Phase1Error::InvalidCurve(_))));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_resume_from_checkpoint() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut checkpoints = vec![];
+    let mut record = |checkpoint: Checkpoint| checkpoints.push(checkpoint);
+    let options = ContributionOptions {
+        checkpoint: Some(&mut record),
+        ..Default::default()
+    };
+    let original =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+
+    let stages: Vec<CheckpointStage> = checkpoints.iter().map(|checkpoint| checkpoint.stage()).collect();
+    assert_eq!(stages, vec![CheckpointStage::KeyGenerated, CheckpointStage::Computed]);
+
+    for checkpoint in &checkpoints {
+        let checkpoint = Checkpoint::from_bytes(&checkpoint.to_bytes()).unwrap();
+        // A different RNG shows the keypair comes from the checkpoint.
+        let options = ContributionOptions {
+            resume: Some(&checkpoint),
+            ..Default::default()
+        };
+        let resumed =
+            contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(1), options).unwrap();
+        assert_eq!(original.response, resumed.response);
+        assert!(verify_response(&challenge, &resumed.response, &parameters).unwrap());
+    }
+
+    // A checkpoint only resumes the contribution to its own challenge.
+    let mut other = challenge.clone();
+    other[0] ^= 1;
+    let options = ContributionOptions {
+        resume: Some(&checkpoints[0]),
+        ..Default::default()
+    };
+    assert!(matches!(
+        contribute_challenge_with_options(&other, &parameters, ChaChaRng::seed_from_u64(0), options),
+        Err(Phase1Error::Checkpoint(_))
+    ));
+
+    assert!(Checkpoint::from_bytes(&checkpoints[0].to_bytes()[..10]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_quick_check() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert!(Phase1WASM::quick_check("bls12_377", "groth16", 2, 2, &result.response).unwrap());
+
+    // Corrupt the second tau_g1 point, which is no longer a point of the subgroup.
+    let mut tampered = result.response.clone();
+    tampered[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    assert!(!Phase1WASM::quick_check("bls12_377", "groth16", 2, 2, &tampered).unwrap());
+
+    assert!(Phase1WASM::quick_check("bls12_377", "groth16", 2, 2, &result.response[1..]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_validate_parameters() {
+    assert!(validate_parameters(ProvingSystem::Groth16, 2, 2, None).is_ok());
+    assert!(validate_parameters(ProvingSystem::Groth16, 2, 2, Some(4)).is_ok());
+    assert!(validate_parameters(ProvingSystem::Marlin, 2, 2, Some(10)).is_ok());
+
+    for (proving_system, power, batch_size, chunk_size) in [
+        (ProvingSystem::Groth16, 0, 2, None),
+        (ProvingSystem::Groth16, MAX_POWER + 1, 2, None),
+        (ProvingSystem::Groth16, 2, 0, None),
+        (ProvingSystem::Groth16, 2, 2, Some(0)),
+        (ProvingSystem::Marlin, 2, 2, Some(9)),
+    ] {
+        assert!(matches!(
+            validate_parameters(proving_system, power, batch_size, chunk_size),
+            Err(Phase1Error::InvalidParameters(_))
+        ));
+    }
+
+    // The entry points reject bad parameters before contributing.
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    assert!(matches!(
+        Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 0, 2, challenge, &[0u8; 32]),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_randomness_commitment() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let first = Phase1WASM::contribute_full("bls12_377", "groth16", 2, 2, challenge.clone(), None, None, None).unwrap();
+    let second =
+        Phase1WASM::contribute_full("bls12_377", "groth16", 2, 2, challenge.clone(), None, None, None).unwrap();
+    let (first, second) = (
+        first.randomness_commitment().unwrap(),
+        second.randomness_commitment().unwrap(),
+    );
+    assert_eq!(first.len(), HASH_LENGTH);
+    assert_ne!(first, second);
+
+    let seeded = Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, challenge, &[0u8; 32]).unwrap();
+    assert!(seeded.randomness_commitment().is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_contribute_full_with_rng() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let contribute = |seed| {
+        Phase1WASM::contribute_full_with_rng("bls12_377", "groth16", 2, 2, &challenge, ChaChaRng::seed_from_u64(seed))
+            .unwrap()
+    };
+    let (first, second) = (contribute(0), contribute(0));
+    assert_eq!(first.response, second.response);
+    assert_ne!(first.response, contribute(1).response);
+    assert!(verify_response(&challenge, &first.response, &parameters).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_json() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = Phase1WASM::contribute_beacon("bls12_377", "groth16", 2, 2, challenge, &[1u8; 32], 4).unwrap();
+
+    let json = result.to_json().unwrap();
+    assert!(json.contains(&hex::encode(result.contribution_hash())));
+
+    let decoded = ContributionResponse::from_json(&json).unwrap();
+    assert_eq!(decoded.current_accumulator_hash(), result.current_accumulator_hash());
+    assert_eq!(decoded.response, result.response);
+    assert_eq!(decoded.contribution_hash(), result.contribution_hash());
+    assert_eq!(decoded.public_key(), result.public_key());
+    assert_eq!(decoded.beacon_seed(), result.beacon_seed());
+    assert_eq!(decoded.randomness_commitment(), None);
+    assert_eq!(decoded.to_json().unwrap(), json);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_format_version() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result.format_version(), RESPONSE_FORMAT_VERSION);
+
+    let json = serde_json::to_string(&result).unwrap();
+    let current = format!(r#""format_version":{}"#, RESPONSE_FORMAT_VERSION);
+    assert!(json.contains(&current));
+    let decoded: ContributionResponse = serde_json::from_str(&json).unwrap();
+    decoded.check_format().unwrap();
+
+    // A response of a later version is rejected by this one.
+    let bumped = json.replace(
+        &current,
+        &format!(r#""format_version":{}"#, RESPONSE_FORMAT_VERSION + 1),
+    );
+    let decoded: ContributionResponse = serde_json::from_str(&bumped).unwrap();
+    assert!(matches!(decoded.check_format(), Err(Phase1Error::UnsupportedFormat(_))));
+
+    let foreign = json.replace(RESPONSE_FORMAT_MAGIC, "another-response");
+    let decoded: ContributionResponse = serde_json::from_str(&foreign).unwrap();
+    assert!(matches!(decoded.check_format(), Err(Phase1Error::UnsupportedFormat(_))));
+
+    // JSON from before the format was recorded reads as the first version.
+    let unversioned = json.replace(
+        &format!(r#""format_magic":"{}",{},"#, RESPONSE_FORMAT_MAGIC, current),
+        "",
+    );
+    assert_ne!(unversioned, json);
+    let decoded: ContributionResponse = serde_json::from_str(&unversioned).unwrap();
+    decoded.check_format().unwrap();
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_from_json_rejects_format() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let json = result.to_json().unwrap();
+    let bumped = json.replace(
+        &format!(r#""format_version":{}"#, RESPONSE_FORMAT_VERSION),
+        &format!(r#""format_version":{}"#, RESPONSE_FORMAT_VERSION + 1),
+    );
+    assert!(ContributionResponse::from_json(&json).is_ok());
+    assert!(matches!(
+        ContributionResponse::from_json(&bumped),
+        Err(Phase1Error::UnsupportedFormat(_))
+    ));
+    assert!(matches!(
+        ContributionResponse::from_json("{"),
+        Err(Phase1Error::Encoding(_))
+    ));
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn test_phase1_write_response() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let expected = result.response.clone();
+
+    let sizes = ParameterSizes::from(&parameters);
+    let out = js_sys::Uint8Array::new_with_length(sizes.response_size(COMPRESSED_OUTPUT) as u32);
+    let result = write_response(result, &out);
+    assert!(result.response.is_empty());
+    assert_eq!(out.to_vec(), expected);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_num_chunks() {
+    for (proving_system, name) in [(ProvingSystem::Groth16, "groth16"), (ProvingSystem::Marlin, "marlin")] {
+        for power in [2, 3, 4] {
+            for chunk_size in [1, 3, 4, 5, 64] {
+                let count = Phase1WASM::num_chunks(name, power, chunk_size).unwrap();
+                let chunks: Vec<usize> = (0..count)
+                    .map(|chunk_index| {
+                        get_parameters_chunked::<Bls12_377>(proving_system, power, 2, chunk_index, chunk_size)
+                            .g1_chunk_size
+                    })
+                    .collect();
+
+                // The chunks cover every element, and only the last one may be partial.
+                let parameters = get_parameters_full::<Bls12_377>(proving_system, power, 2);
+                assert_eq!(chunks.iter().sum::<usize>(), parameters.g1_chunk_size);
+                assert!(chunks[..count - 1].iter().all(|&size| size == chunk_size));
+                assert!(chunks[count - 1] > 0 && chunks[count - 1] <= chunk_size);
+            }
+        }
+    }
+    // With 7 tau_g1 elements, chunks of 4 leave a partial last chunk of 3.
+    assert_eq!(Phase1WASM::num_chunks("groth16", 2, 4).unwrap(), 2);
+    assert!(Phase1WASM::num_chunks("groth16", 2, 0).is_err());
+    assert!(matches!(
+        num_chunks(ProvingSystem::Groth16, 2, 0),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_pool_chunk_index_out_of_range() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let count = num_chunks(ProvingSystem::Groth16, power, chunk_size).unwrap();
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, 0, chunk_size);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let pool = Phase1Pool::new_native(1).unwrap();
+    let result = pool.contribute_chunk(
+        "bls12_377",
+        "groth16",
+        batch_size,
+        power,
+        count,
+        chunk_size,
+        &[0u8; 32],
+        challenge,
+        None,
+        None,
+        None,
+        None,
+    );
+    match result {
+        Err(Phase1Error::ChunkIndexOutOfRange { index, num_chunks }) => assert_eq!((index, num_chunks), (count, count)),
+        _ => panic!("expected a chunk index error"),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_pool_chunk_range() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let pool = Phase1Pool::new_native(2).unwrap();
+
+    let parameters: Vec<_> = (0..2)
+        .map(|chunk_index| {
+            get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size)
+        })
+        .collect();
+    let challenges: Vec<Vec<u8>> = parameters
+        .iter()
+        .map(|parameters| generate_input(parameters, COMPRESSED_INPUT).0)
+        .collect();
+    let seeds = vec![vec![0u8; 32], vec![1u8; 32]];
+
+    let responses = pool
+        .contribute_chunks(
+            "bls12_377",
+            "groth16",
+            batch_size,
+            power,
+            chunk_size,
+            0..2,
+            &seeds,
+            challenges.clone(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(responses.len(), 2);
+    for ((result, challenge), parameters) in responses.iter().zip(&challenges).zip(&parameters) {
+        assert!(verify_response(challenge, &result.response, parameters).unwrap());
+    }
+
+    match pool.contribute_chunks(
+        "bls12_377",
+        "groth16",
+        batch_size,
+        power,
+        chunk_size,
+        0..2,
+        &seeds[..1],
+        challenges,
+        None,
+    ) {
+        Err(Phase1Error::ChunkCountMismatch { expected, actual }) => assert_eq!((expected, actual), (2, 1)),
+        _ => panic!("expected a chunk count error"),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_progress_reporter() {
+    let (power, batch_size, chunk_size) = (2, 2, 4);
+    let pool = Phase1Pool::new_native(2).unwrap();
+
+    let challenges: Vec<Vec<u8>> = (0..2)
+        .map(|chunk_index| {
+            let parameters =
+                get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, power, batch_size, chunk_index, chunk_size);
+            generate_input(&parameters, COMPRESSED_INPUT).0
+        })
+        .collect();
+    let seeds = vec![vec![0u8; 32], vec![1u8; 32]];
+
+    let reporter = ProgressReporter::new(2);
+    assert_eq!(reporter.fraction(), 0.0);
+    pool.contribute_chunks(
+        "bls12_377",
+        "groth16",
+        batch_size,
+        power,
+        chunk_size,
+        0..2,
+        &seeds,
+        challenges,
+        Some(&reporter.handle()),
+    )
+    .unwrap();
+    assert_eq!(reporter.fraction(), 1.0);
+    assert_eq!(
+        unsafe { *(reporter.completed_ptr() as *const u32) },
+        reporter.total_steps()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_challenge_length_preflight() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    assert_eq!(expected_challenge_len(&parameters, COMPRESSED_INPUT), challenge.len());
+
+    let error = contribute_challenge(&challenge[1..], &parameters, ChaChaRng::seed_from_u64(0))
+        .err()
+        .unwrap();
+    let value = Phase1ErrorValue::from(error);
+    assert_eq!(value.code(), 0);
+    assert_eq!(value.expected(), Some(challenge.len()));
+    assert_eq!(value.actual(), Some(challenge.len() - 1));
+
+    let value = Phase1ErrorValue::from(Phase1Error::Cancelled);
+    assert_eq!((value.expected(), value.actual()), (None, None));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_iterated_hash() {
+    assert_eq!(iterated_hash(b"abc", 0), b"abc".to_vec());
+    assert_eq!(
+        hex::encode(iterated_hash(b"abc", 1)),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex::encode(iterated_hash(b"abc", 2)),
+        "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358"
+    );
+    assert_eq!(iterated_hash(b"abc", 16), beacon_seed(b"abc", 16));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_attestation_is_deterministic() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = Phase1WASM::contribute_beacon("bls12_377", "groth16", 2, 2, challenge, &[1u8; 32], 4).unwrap();
+
+    let summary = ParametersSummary::new("BLS12_377", "Groth16", 2, 2).unwrap();
+    let attestation = build_attestation(&result, &summary);
+    let bytes = attestation.to_bytes().unwrap();
+    assert_eq!(bytes, build_attestation(&result, &summary).to_bytes().unwrap());
+    assert_eq!(
+        bytes,
+        build_attestation(&result, &ParametersSummary::new("bls12_377", "groth16", 2, 2).unwrap())
+            .to_bytes()
+            .unwrap()
+    );
+
+    let expected = format!(
+        r#"{{"batch_size":2,"contribution_hash":"{}","curve":"bls12_377","power":2,"previous_hash":"{}","proving_system":"groth16","public_key":"{}"}}"#,
+        hex::encode(result.contribution_hash()),
+        hex::encode(result.current_accumulator_hash()),
+        hex::encode(result.public_key()),
+    );
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+    assert_eq!(Attestation::from_json(&expected).unwrap(), attestation);
+    assert!(matches!(Attestation::from_json("{"), Err(Phase1Error::Encoding(_))));
+
+    assert!(ParametersSummary::new("bls12_377", "groth16", 0, 2).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_check_response_links() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert!(check_response_links(&challenge, &result.response, &parameters, COMPRESSED_OUTPUT).unwrap());
+    assert!(Phase1WASM::check_links("bls12_377", "groth16", 2, 2, &challenge, &result.response).unwrap());
+
+    // A challenge of another accumulator, which only differs in the hash it records.
+    let mut other_challenge = challenge.clone();
+    other_challenge[0] ^= 0xff;
+    assert!(!check_response_links(&other_challenge, &result.response, &parameters, COMPRESSED_OUTPUT).unwrap());
+    let other = contribute_challenge(&other_challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert!(!check_response_links(&challenge, &other.response, &parameters, COMPRESSED_OUTPUT).unwrap());
+
+    assert!(check_response_links(&challenge, &result.response[1..], &parameters, COMPRESSED_OUTPUT).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_core_contribute() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut response = vec![];
+    let contribution = contribution_core::contribute(
+        &challenge,
+        &parameters,
+        COMPRESSED_INPUT,
+        COMPRESSED_OUTPUT,
+        CHECK_INPUT_CORRECTNESS,
+        ChaChaRng::seed_from_u64(0),
+        &mut response,
+    )
+    .unwrap();
+    assert!(verify_response(&challenge, &response, &parameters).unwrap());
+
+    // The WASM layer contributes with the same steps.
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result.response, response);
+    assert_eq!(result.current_accumulator_hash(), contribution.current_accumulator_hash);
+    assert_eq!(result.contribution_hash(), contribution.contribution_hash);
+    assert_eq!(result.public_key(), contribution.public_key);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_core_hashes_eq() {
+    let hash = calculate_hash(b"challenge");
+    assert!(contribution_core::hashes_eq(&hash, &hash.to_vec()));
+    assert!(contribution_core::hashes_eq(&[], &[]));
+
+    for index in [0, 31, 63].iter() {
+        let mut other = hash.to_vec();
+        other[*index] ^= 1;
+        assert!(!contribution_core::hashes_eq(&hash, &other));
+    }
+    assert!(!contribution_core::hashes_eq(&hash, &hash[..32]));
+    assert!(!contribution_core::hashes_eq(&hash, &calculate_hash(b"response")));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_core_contribute_with_keypair() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let current_accumulator_hash = calculate_hash(&challenge);
+    let (public_key, private_key) =
+        contribution_core::generate_keypair(&mut ChaChaRng::seed_from_u64(0), &current_accumulator_hash).unwrap();
+
+    let contribute = |public_key: &phase1::PublicKey<Bls12_377>, private_key: &phase1::PrivateKey<Bls12_377>| {
+        let mut response = vec![];
+        contribution_core::contribute_with_keypair(
+            &challenge,
+            &parameters,
+            COMPRESSED_INPUT,
+            COMPRESSED_OUTPUT,
+            CHECK_INPUT_CORRECTNESS,
+            public_key,
+            private_key,
+            &mut response,
+        )
+        .map(|contribution| (contribution, response))
+    };
+    let (contribution, response) = contribute(&public_key, &private_key).unwrap();
+    assert!(verify_response(&challenge, &response, &parameters).unwrap());
+
+    // The keypair the RNG generates gives the contribution made with the RNG.
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result.response, response);
+    assert_eq!(result.contribution_hash(), contribution.contribution_hash);
+
+    let (_, other_private_key) =
+        contribution_core::generate_keypair(&mut ChaChaRng::seed_from_u64(1), &current_accumulator_hash).unwrap();
+    assert!(matches!(
+        contribute(&public_key, &other_private_key),
+        Err(Phase1Error::KeypairMismatch)
+    ));
+
+    let (other_public_key, other_private_key) =
+        contribution_core::generate_keypair(&mut ChaChaRng::seed_from_u64(0), &blank_hash()).unwrap();
+    assert!(matches!(
+        contribute(&other_public_key, &other_private_key),
+        Err(Phase1Error::KeypairMismatch)
+    ));
+}
+
+#[cfg(all(feature = "zeroize", not(target_arch = "wasm32")))]
+#[test]
+fn test_phase1_secret_key_zeroized_on_drop() {
+    use snarkvm_fields::Zero;
+    use std::mem::ManuallyDrop;
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let (_, private_key): (_, phase1::PrivateKey<Bls12_377>) =
+        contribution_core::generate_keypair(&mut ChaChaRng::seed_from_u64(0), &calculate_hash(&challenge)).unwrap();
+    assert_ne!(private_key.tau, <Bls12_377 as PairingEngine>::Fr::zero());
+
+    let mut secret_key = ManuallyDrop::new(contribution_core::SecretKey::from(private_key));
+    unsafe { ManuallyDrop::drop(&mut secret_key) };
+    // The storage of a `ManuallyDrop` outlives the drop, so it can still be read.
+    let bytes = unsafe {
+        std::slice::from_raw_parts(
+            &secret_key as *const ManuallyDrop<contribution_core::SecretKey<Bls12_377>> as *const u8,
+            std::mem::size_of::<contribution_core::SecretKey<Bls12_377>>(),
+        )
+    };
+    assert!(bytes.iter().all(|byte| *byte == 0));
+}
+
+// Filling the compressed response of a BLS12-377 Groth16 ceremony, measured on
+// its own natively (best of 5 on one core), takes around 2ms at power 16, 43ms
+// at power 18 and 126ms at power 20. Key generation now overlaps with the fill,
+// which saves the shorter of the two whenever a second thread is free.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_concurrent_fill_matches_sequential() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    let worker = thread_pool
+        .install(|| contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)))
+        .unwrap();
+    let caller = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(worker.response, caller.response);
+    assert_eq!(worker.contribution_hash(), caller.contribution_hash());
+    assert!(verify_response(&challenge, &worker.response, &parameters).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_full_via_chunked() {
+    for proving_system in &[ProvingSystem::Groth16, ProvingSystem::Marlin] {
+        let full = get_parameters_full::<Bls12_377>(*proving_system, 3, 2);
+        let chunked =
+            get_parameters_chunked_with_mode::<Bls12_377>(ContributionMode::Full, *proving_system, 3, 2, 0, 16);
+        assert_eq!(chunked.contribution_mode, ContributionMode::Full);
+        assert_eq!(chunked.g1_chunk_size, full.g1_chunk_size);
+        assert_eq!(chunked.other_chunk_size, full.other_chunk_size);
+        assert_eq!(chunked.accumulator_size, full.accumulator_size);
+        assert_eq!(chunked.contribution_size, full.contribution_size);
+        assert_eq!(chunked.public_key_size, full.public_key_size);
+    }
+
+    let chunked_mode =
+        get_parameters_chunked_with_mode::<Bls12_377>(ContributionMode::Chunked, ProvingSystem::Groth16, 3, 2, 1, 4);
+    let chunked = get_parameters_chunked::<Bls12_377>(ProvingSystem::Groth16, 3, 2, 1, 4);
+    assert_eq!(chunked_mode.contribution_mode, chunked.contribution_mode);
+    assert_eq!(chunked_mode.accumulator_size, chunked.accumulator_size);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_native_contribute_from_path() {
+    use crate::native::NativeContributor;
+    use phase1::helpers::CurveKind;
+
+    let contributor = NativeContributor::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 2, 2);
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let directory = std::env::temp_dir();
+    let challenge_path = directory.join(format!("phase1-wasm-{}-challenge", std::process::id()));
+    let response_path = directory.join(format!("phase1-wasm-{}-response", std::process::id()));
+    std::fs::write(&challenge_path, &challenge).unwrap();
+    let _ = std::fs::remove_file(&response_path);
+
+    let from_path = contributor
+        .contribute_full_from_path(&challenge_path, &response_path, ChaChaRng::seed_from_u64(0))
+        .unwrap();
+    let response = std::fs::read(&response_path).unwrap();
+    let in_memory = contributor
+        .contribute_full(&challenge, ChaChaRng::seed_from_u64(0))
+        .unwrap();
+    assert_eq!(response, in_memory.response);
+    assert!(from_path.response.is_empty());
+    assert_eq!(from_path.contribution_hash(), in_memory.contribution_hash());
+    assert_eq!(from_path.public_key(), in_memory.public_key());
+
+    // The response file isn't overwritten.
+    assert!(matches!(
+        contributor.contribute_full_from_path(&challenge_path, &response_path, ChaChaRng::seed_from_u64(0)),
+        Err(Phase1Error::Io(_))
+    ));
+
+    std::fs::remove_file(&challenge_path).unwrap();
+    std::fs::remove_file(&response_path).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_parameters_fingerprint() {
+    let fingerprint = parameters_fingerprint("bls12_377", "groth16", 2, 4).unwrap();
+    assert_eq!(fingerprint.len(), 32);
+    assert_eq!(
+        parameters_fingerprint("BLS12_377", "Groth16", 2, 4).unwrap(),
+        fingerprint
+    );
+
+    for other in &[
+        parameters_fingerprint("bw6", "groth16", 2, 4),
+        parameters_fingerprint("bls12_377", "marlin", 2, 4),
+        parameters_fingerprint("bls12_377", "groth16", 3, 4),
+        parameters_fingerprint("bls12_377", "groth16", 2, 5),
+    ] {
+        assert_ne!(other.as_ref().unwrap(), &fingerprint);
+    }
+
+    assert!(matches!(
+        parameters_fingerprint("bn254", "groth16", 2, 4),
+        Err(Phase1Error::InvalidCurve(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_and_next_hash_chain() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let first = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let first_hash =
+        Phase1WASM::verify_and_next_hash("bls12_377", "groth16", 2, 2, &challenge, &first.response).unwrap();
+    let next = next_challenge(&first.response, &parameters).unwrap();
+    assert_eq!(calculate_hash(&next).to_vec(), first_hash);
+
+    let second = contribute_challenge(&next, &parameters, ChaChaRng::seed_from_u64(1)).unwrap();
+    assert_eq!(second.current_accumulator_hash(), first_hash);
+    let second_hash = verify_response_and_next_hash(&next, &second.response, &parameters).unwrap();
+    assert_eq!(
+        calculate_hash(&next_challenge(&second.response, &parameters).unwrap()).to_vec(),
+        second_hash
+    );
+
+    // The second response isn't a contribution to the first challenge.
+    assert!(matches!(
+        verify_response_and_next_hash(&challenge, &second.response, &parameters),
+        Err(Phase1Error::InvalidContribution)
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_next_challenge() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let first = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let next = Phase1WASM::next_challenge("bls12_377", "groth16", 2, 2, &first.response).unwrap();
+    assert_eq!(next.len(), parameters.accumulator_size);
+    assert_eq!(next, next_challenge(&first.response, &parameters).unwrap());
+    assert_eq!(
+        &next[..parameters.hash_size],
+        calculate_hash(&first.response).as_slice()
+    );
+
+    let second = contribute_challenge(&next, &parameters, ChaChaRng::seed_from_u64(1)).unwrap();
+    assert_eq!(second.current_accumulator_hash(), calculate_hash(&next).to_vec());
+    assert!(verify_response(&next, &second.response, &parameters).unwrap());
+
+    assert!(matches!(
+        Phase1WASM::next_challenge("bls12_377", "groth16", 2, 2, &challenge),
+        Err(Phase1Error::ResponseLength { .. })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_transcript() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (initial_challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    let mut challenge = initial_challenge.clone();
+    let mut responses = vec![];
+    for seed in 0..3 {
+        let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(seed)).unwrap();
+        challenge = next_challenge(&result.response, &parameters).unwrap();
+        responses.push(result.response);
+    }
+    assert!(verify_transcript(&initial_challenge, &responses, &parameters).unwrap());
+    assert!(verify_transcript(&initial_challenge, &[], &parameters).unwrap());
+
+    // Corrupt the second tau_g1 point of the middle contribution.
+    let mut corrupted = responses.clone();
+    corrupted[1][parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    match verify_transcript(&initial_challenge, &corrupted, &parameters) {
+        Err(Phase1Error::TranscriptContribution { index, .. }) => assert_eq!(index, 1),
+        _ => panic!("expected the middle contribution to fail"),
+    }
+
+    // Responses out of order don't link to the challenges before them.
+    responses.swap(0, 1);
+    match verify_transcript(&initial_challenge, &responses, &parameters) {
+        Err(Phase1Error::TranscriptContribution { index, .. }) => assert_eq!(index, 0),
+        _ => panic!("expected the first contribution to fail"),
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[wasm_bindgen_test]
+fn test_phase1_transport_compression() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    assert_eq!(
+        decompress_response(&result.compressed_bytes().unwrap()).unwrap(),
+        result.response
+    );
+    for level in [1, 19] {
+        let compressed = result.compressed_bytes_with_level(level).unwrap();
+        assert_eq!(decompress_response(&compressed).unwrap(), result.response);
+    }
+
+    // The uncompressed response isn't a zstd frame.
+    assert!(matches!(
+        decompress_response(&result.response),
+        Err(Phase1Error::TransportCompression(_))
+    ));
+}
+
+#[cfg(all(feature = "nodejs", target_arch = "wasm32"))]
+#[wasm_bindgen_test]
+fn test_phase1_node_buffers() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let response = js_sys::Uint8Array::new(&result.response_buffer());
+    assert_eq!(response.to_vec(), result.response);
+    let contribution_hash = js_sys::Uint8Array::new(&result.contribution_hash_buffer());
+    assert_eq!(contribution_hash.to_vec(), result.contribution_hash());
+    let public_key = js_sys::Uint8Array::new(&result.public_key_buffer());
+    assert_eq!(public_key.to_vec(), result.public_key());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_then_contribute() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (prior_challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let prior = contribute_challenge(&prior_challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let result = Phase1WASM::verify_then_contribute(
+        "bls12_377",
+        "groth16",
+        2,
+        2,
+        &prior_challenge,
+        &prior.response,
+        ChaChaRng::seed_from_u64(1),
+    )
+    .unwrap();
+    let challenge = next_challenge(&prior.response, &parameters).unwrap();
+    assert_eq!(result.current_accumulator_hash(), calculate_hash(&challenge).to_vec());
+    assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+
+    // Corrupt the second tau_g1 point of the prior response.
+    let mut invalid = prior.response.clone();
+    invalid[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    assert!(matches!(
+        verify_then_contribute(&prior_challenge, &invalid, &parameters, ChaChaRng::seed_from_u64(1)),
+        Err(Phase1Error::PriorContributionInvalid)
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_parse_response() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let parsed = Phase1WASM::parse_response("bls12_377", "groth16", 2, 2, &result.response).unwrap();
+    assert_eq!(parsed.challenge_hash(), result.current_accumulator_hash());
+    assert!(parsed.is_compressed());
+    assert_eq!(
+        parsed.body_length(),
+        parameters.contribution_size - parameters.hash_size - parameters.public_key_size
+    );
+    assert_eq!(parsed.tau_powers_g1_length(), parameters.powers_g1_length);
+    assert_eq!(parsed.tau_powers_length(), parameters.powers_length);
+    assert_eq!(parsed.public_key(), result.public_key());
+
+    let components = [
+        parsed.public_key_tau_g1(),
+        parsed.public_key_alpha_g1(),
+        parsed.public_key_beta_g1(),
+        parsed.public_key_tau_g2(),
+        parsed.public_key_alpha_g2(),
+        parsed.public_key_beta_g2(),
+    ];
+    assert_eq!(components.concat(), result.public_key());
+
+    match parse_response(&result.response[1..], &parameters) {
+        Err(Phase1Error::Malformed(_)) => {}
+        _ => panic!("expected a malformed response error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_parse_uncompressed_response() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let options = ContributionOptions {
+        serialization: SerializationOptions {
+            compressed_output: UseCompression::No,
+            ..Default::default()
+        },
+        public_key_compression: UseCompression::No,
+        ..Default::default()
+    };
+    let result =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+
+    let parsed = parse_response(&result.response, &parameters).unwrap();
+    let public_key_size = contribution_core::public_key_len(&parameters, UseCompression::No);
+    assert!(!parsed.is_compressed());
+    assert!(!parsed.is_public_key_compressed());
+    assert_eq!(parsed.body_length(), parameters.accumulator_size - parameters.hash_size);
+    assert_eq!(
+        parsed.public_key(),
+        result.response[result.response.len() - public_key_size..].to_vec()
+    );
+
+    // The uncompressed elements of the key are sliced at their own offsets.
+    let (g1_size, g2_size) = (parameters.curve.g1_size, parameters.curve.g2_size);
+    assert_eq!(parsed.public_key_tau_g1().len(), 2 * g1_size);
+    assert_eq!(parsed.public_key_beta_g2().len(), g2_size);
+    assert_eq!(
+        parsed.public_key_beta_g2(),
+        parsed.public_key()[6 * g1_size + 2 * g2_size..].to_vec()
+    );
+    let components = [
+        parsed.public_key_tau_g1(),
+        parsed.public_key_alpha_g1(),
+        parsed.public_key_beta_g1(),
+        parsed.public_key_tau_g2(),
+        parsed.public_key_alpha_g2(),
+        parsed.public_key_beta_g2(),
+    ];
+    assert_eq!(components.concat(), parsed.public_key());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_hash_algorithms() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (initial_challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    assert_eq!(
+        HashAlgorithm::default().hash(&initial_challenge),
+        calculate_hash(&initial_challenge).to_vec()
+    );
+    let sha256 = HashAlgorithm::Sha256.hash(&initial_challenge);
+    assert_eq!(sha256.len(), HashAlgorithm::Sha256.digest_len());
+    assert_eq!(sha256, Sha256::digest(&initial_challenge).to_vec());
+
+    for hash_algorithm in [HashAlgorithm::Blake2b, HashAlgorithm::Sha256] {
+        let mut challenge = initial_challenge.clone();
+        let mut responses = vec![];
+        for seed in 0..2 {
+            let options = ContributionOptions {
+                hash_algorithm,
+                ..Default::default()
+            };
+            let result =
+                contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(seed), options)
+                    .unwrap();
+            assert_eq!(result.current_accumulator_hash(), hash_algorithm.hash(&challenge));
+            assert_eq!(result.contribution_hash(), hash_algorithm.hash(&result.response));
+            assert!(verify_response_with_hash(
+                &challenge,
+                &result.response,
+                &parameters,
+                SerializationOptions::default(),
+                hash_algorithm
+            )
+            .unwrap());
+
+            challenge = next_challenge_with_hash(&result.response, &parameters, hash_algorithm).unwrap();
+            assert_eq!(
+                challenge[..HASH_LENGTH],
+                contribution_core::transcript_hash(&result.contribution_hash())[..]
+            );
+            responses.push(result.response);
+        }
+
+        // Only the Blake2b chain verifies without the hash algorithm.
+        assert_eq!(
+            verify_response(&initial_challenge, &responses[0], &parameters).unwrap(),
+            hash_algorithm == HashAlgorithm::Blake2b
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_power_too_large() {
+    let error = match Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 256, 1000, vec![], &[0u8; 32]) {
+        Err(error) => error,
+        Ok(_) => panic!("expected a power too large error"),
+    };
+    assert!(matches!(
+        error,
+        Phase1Error::PowerTooLarge {
+            requested: 1000,
+            max: MAX_SUPPORTED_POWER
+        }
+    ));
+    let value = Phase1ErrorValue::from(error);
+    assert_eq!(
+        (value.expected(), value.actual()),
+        (Some(MAX_SUPPORTED_POWER), Some(1000))
+    );
+
+    // A chunk only holds its chunk of the powers, so it isn't limited by the power.
+    assert!(matches!(
+        Phase1WASM::verify_chunked(
+            "bls12_377",
+            "groth16",
+            256,
+            MAX_SUPPORTED_POWER + 1,
+            0,
+            4,
+            &[],
+            &[],
+            None
+        ),
+        Err(Phase1Error::ChallengeLength { .. })
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_native_max_power() {
+    use crate::native::NativeContributor;
+    use phase1::helpers::CurveKind;
+
+    let contributor = NativeContributor::new(CurveKind::Bls12_377, ProvingSystem::Groth16, 24, 256);
+    assert!(matches!(
+        contributor.contribute_full(&[], ChaChaRng::seed_from_u64(0)),
+        Err(Phase1Error::PowerTooLarge {
+            requested: 24,
+            max: MAX_SUPPORTED_POWER
+        })
+    ));
+
+    // Raised for a server, the power is accepted, and only the empty challenge is rejected.
+    assert!(matches!(
+        contributor
+            .max_power(MAX_POWER)
+            .contribute_full(&[], ChaChaRng::seed_from_u64(0)),
+        Err(Phase1Error::EmptyChallenge)
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_derive_chunk_seed() {
+    let master_seed = [7u8; 32];
+    let first = derive_chunk_seed(&master_seed, 0);
+    assert_eq!(first.len(), MIN_SEED_LENGTH);
+    assert_eq!(first, derive_chunk_seed(&master_seed, 0));
+    assert_ne!(first, derive_chunk_seed(&master_seed, 1));
+    assert_ne!(first, derive_chunk_seed(&[8u8; 32], 0));
+    assert_ne!(first, master_seed.to_vec());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_self_test() {
+    assert!(self_test().is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_uploaded() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let chunk_len = 100;
+    let hashes = result.chunk_hashes(chunk_len).unwrap();
+    assert_eq!(hashes.len(), (result.response.len() + chunk_len - 1) / chunk_len);
+    assert_eq!(hashes[0], calculate_hash(&result.response[..chunk_len]).to_vec());
+
+    let mut uploaded: Vec<Vec<u8>> = result.response.chunks(chunk_len).map(|chunk| chunk.to_vec()).collect();
+    assert!(result.verify_uploaded(chunk_len, &uploaded).unwrap().is_empty());
+
+    uploaded[1][5] ^= 0xff;
+    assert_eq!(result.verify_uploaded(chunk_len, &uploaded).unwrap(), vec![1]);
+
+    // The chunks which haven't been uploaded yet are to be uploaded too.
+    assert_eq!(
+        result.verify_uploaded(chunk_len, &uploaded[..1]).unwrap(),
+        (1..hashes.len()).collect::<Vec<_>>()
+    );
+
+    // Chunks of no bytes are rejected rather than panicking.
+    assert!(matches!(result.chunk_hashes(0), Err(Phase1Error::InvalidParameters(_))));
+    assert!(matches!(
+        result.verify_uploaded(0, &uploaded),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+    assert!(result.chunk_hashes_array(0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_estimate_duration() {
+    let calibration = Phase1WASM::calibrate("bls12_377", "groth16").unwrap();
+    assert!(calibration > 0.0);
+
+    // 2^4 powers give 31 tau_g1 elements, which take 4 batches of 8.
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 4, 8);
+    assert_eq!(num_batches(&parameters), 4);
+    let estimate = Phase1WASM::estimate_duration_ms("bls12_377", "groth16", 8, 4, calibration).unwrap();
+    assert_eq!(estimate, 4.0 * calibration);
+
+    assert!(matches!(
+        Phase1WASM::estimate_duration_ms("bls12_377", "groth16", 8, 4, -1.0),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_diff_responses() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let public_key_position = result.response.len() - parameters.public_key_size;
+
+    let same = diff_responses(&result.response, &result.response.clone(), &parameters);
+    assert_eq!(same.first_difference(), None);
+    assert!(same.bodies_match() && same.public_keys_match());
+
+    let mut other_key = result.response.clone();
+    other_key[public_key_position + 3] ^= 0xff;
+    let diff = diff_responses(&result.response, &other_key, &parameters);
+    assert_eq!(diff.first_difference(), Some(public_key_position + 3));
+    assert!(diff.bodies_match());
+    assert!(!diff.public_keys_match());
+    assert_eq!(
+        (diff.a_length(), diff.b_length()),
+        (result.response.len(), other_key.len())
+    );
+
+    let mut other_body = result.response.clone();
+    other_body[parameters.hash_size] ^= 0xff;
+    let diff = diff_responses(&result.response, &other_body, &parameters);
+    assert_eq!(diff.first_difference(), Some(parameters.hash_size));
+    assert!(!diff.bodies_match());
+    assert!(diff.public_keys_match());
+
+    let truncated = &result.response[..result.response.len() - 1];
+    let diff = Phase1WASM::diff_responses("bls12_377", "groth16", 2, 2, &result.response, truncated).unwrap();
+    assert_eq!(diff.first_difference(), Some(truncated.len()));
+    assert_eq!(diff.b_length(), truncated.len());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_reserve_memory() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 10, 256);
+    reserve_memory(estimate_contribution_memory(&parameters)).unwrap();
+    reserve_memory(0).unwrap();
+
+    #[cfg(target_arch = "wasm32")]
+    assert!(matches!(
+        reserve_memory(usize::MAX),
+        Err(Phase1Error::MemoryReservation(_))
+    ));
+
+    // Reserving up front leaves the contribution unchanged.
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let options = ContributionOptions {
+        reserve_memory: false,
+        ..Default::default()
+    };
+    let unreserved =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(unreserved.response, result.response);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_suggest_batch_size() {
+    let power = 10;
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, MIN_SUGGESTED_BATCH_SIZE);
+
+    // A tight budget, which the challenge alone exceeds, still gets the smallest batch.
+    assert_eq!(suggest_batch_size_for(&parameters, 0), MIN_SUGGESTED_BATCH_SIZE);
+    assert_eq!(
+        Phase1WASM::suggest_batch_size("bls12_377", "groth16", power, parameters.accumulator_size).unwrap(),
+        MIN_SUGGESTED_BATCH_SIZE
+    );
+
+    // A budget which fits a few hundred elements gets the largest batch which fits.
+    let budget = estimate_contribution_memory(&get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, 300));
+    assert_eq!(suggest_batch_size_for(&parameters, budget), 300);
+    assert!(
+        estimate_contribution_memory(&get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, power, 301)) > budget
+    );
+
+    // A generous budget is capped at the tau_g1 powers, or at the largest suggestion.
+    assert_eq!(
+        suggest_batch_size_for(&parameters, usize::MAX),
+        parameters.powers_g1_length
+    );
+    let large = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 20, MIN_SUGGESTED_BATCH_SIZE);
+    assert_eq!(suggest_batch_size_for(&large, usize::MAX), MAX_SUGGESTED_BATCH_SIZE);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_verify_detailed() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let serialization = SerializationOptions::default();
+    let all_ok = VerificationReport {
+        length_ok: true,
+        subgroup_ok: true,
+        ratio_check_ok: true,
+        hash_link_ok: true,
+    };
+
+    let report = verify_detailed(&challenge, &result.response, &parameters, serialization);
+    assert_eq!(report, all_ok);
+    assert!(report.is_valid());
+    assert_eq!(
+        Phase1WASM::verify_detailed("bls12_377", "groth16", 2, 2, &challenge, &result.response).unwrap(),
+        all_ok
+    );
+
+    // A trailing byte, which the other checks ignore.
+    let mut longer = result.response.clone();
+    longer.push(0);
+    let report = verify_detailed(&challenge, &longer, &parameters, serialization);
+    assert_eq!(
+        report,
+        VerificationReport {
+            length_ok: false,
+            ..all_ok
+        }
+    );
+    assert!(!report.is_valid());
+
+    // A response recording another challenge's hash.
+    let mut unlinked = result.response.clone();
+    unlinked[0] ^= 0xff;
+    assert_eq!(
+        verify_detailed(&challenge, &unlinked, &parameters, serialization),
+        VerificationReport {
+            hash_link_ok: false,
+            ..all_ok
+        }
+    );
+
+    // The public key of another contribution, whose secrets didn't transform the challenge.
+    let other = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(1)).unwrap();
+    let mut swapped_key = result.response.clone();
+    let public_key_position = swapped_key.len() - parameters.public_key_size;
+    swapped_key[public_key_position..].copy_from_slice(&other.public_key());
+    assert_eq!(
+        verify_detailed(&challenge, &swapped_key, &parameters, serialization),
+        VerificationReport {
+            ratio_check_ok: false,
+            ..all_ok
+        }
+    );
+
+    // A point outside its subgroup can't be the right power either, so the ratio
+    // checks fail along with the subgroup check, while the rest still pass.
+    let mut outside_subgroup = result.response.clone();
+    outside_subgroup[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    let report = verify_detailed(&challenge, &outside_subgroup, &parameters, serialization);
+    assert!(!report.subgroup_ok);
+    assert!(report.length_ok && report.hash_link_ok);
+
+    // A truncated response fails every check which needs the whole of it, without panicking.
+    assert_eq!(
+        verify_detailed(&challenge, &result.response[..10], &parameters, serialization),
+        VerificationReport::default()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribute_from_compressed_challenge() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, UseCompression::Yes);
+    assert_eq!(
+        challenge.len(),
+        parameters.contribution_size - parameters.public_key_size
+    );
+
+    let serialization = SerializationOptions::compressed_challenge();
+    let options = ContributionOptions {
+        serialization,
+        ..Default::default()
+    };
+    let result =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+    assert_eq!(result.response.len(), parameters.contribution_size);
+    assert!(verify_response_with_options(&challenge, &result.response, &parameters, serialization).unwrap());
+
+    // An uncompressed challenge is rejected on its length, which is that of an uncompressed challenge.
+    let (uncompressed, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let options = ContributionOptions {
+        serialization,
+        ..Default::default()
+    };
+    match contribute_challenge_with_options(&uncompressed, &parameters, ChaChaRng::seed_from_u64(0), options) {
+        Err(Phase1Error::WrongCompression {
+            expected,
+            compressed: false,
+            ..
+        }) => assert_eq!(expected, challenge.len()),
+        _ => panic!("expected a challenge compression error"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_response_without_public_key() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let accumulator = result.response_without_public_key();
+    let public_key = result.response_public_key();
+    assert_eq!(public_key.len(), parameters.public_key_size);
+    assert_eq!(public_key, result.public_key());
+    assert_eq!([accumulator, public_key].concat(), result.response);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_contribution_logging() {
+    use log::Level;
+    use std::{
+        sync::Mutex,
+        thread::{self, ThreadId},
+    };
+
+    // Records the messages of this crate with the thread they were logged on,
+    // since the other tests contribute concurrently.
+    struct CapturingLogger(Mutex<Vec<(ThreadId, Level, String)>>);
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            if record.target().starts_with("phase1_wasm") {
+                let message = (thread::current().id(), record.level(), record.args().to_string());
+                self.0.lock().unwrap().push(message);
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let messages: Vec<(Level, String)> = LOGGER
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(thread, _, _)| *thread == thread::current().id())
+        .map(|(_, level, message)| (*level, message.clone()))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                Level::Debug,
+                format!("Checked the challenge of {} bytes", challenge.len())
+            ),
+            (
+                Level::Debug,
+                format!(
+                    "Computed the challenge hash {}",
+                    hex::encode(calculate_hash(&challenge))
+                )
+            ),
+            (Level::Debug, "Generated the keypair".to_string()),
+            (Level::Info, "Completed the computation of the contribution".to_string()),
+            (Level::Debug, "Wrote the public key".to_string()),
+        ]
+    );
+}
+
+#[cfg(all(feature = "wasi", target_os = "wasi"))]
+#[test]
+fn test_phase1_wasi_system_randomness() {
+    let randomness = system_randomness().unwrap();
+    assert_eq!(randomness.len(), HASH_LENGTH);
+    assert_ne!(randomness, system_randomness().unwrap());
+    assert_ne!(randomness, calculate_hash(&[0; 1024]).to_vec());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_power_counts() {
+    for power in [1, 2, 5, 10] {
+        let counts = Phase1WASM::power_counts("groth16", power).unwrap();
+        assert_eq!(counts.tau_powers_g1(), (1 << (power + 1)) - 1);
+        assert_eq!(counts.tau_powers_g2(), 1 << power);
+        assert_eq!(counts.alpha_tau(), 1 << power);
+        assert_eq!(counts.beta_tau(), 1 << power);
+    }
+
+    let counts = Phase1WASM::power_counts("marlin", 4).unwrap();
+    assert_eq!(counts.tau_powers_g1(), 1 << 4);
+    assert_eq!(counts.beta_tau(), 0);
+
+    assert!(matches!(
+        Phase1WASM::power_counts("plonk", 4),
+        Err(Phase1Error::InvalidProvingSystem(_))
+    ));
+}
+
+/// An RNG which records how many draws were made from it.
+struct CountingRng {
+    rng: ChaChaRng,
+    draws: usize,
+}
+
+impl rand::RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += 1;
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draws += 1;
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl rand::CryptoRng for CountingRng {}
+
+#[wasm_bindgen_test]
+fn test_phase1_key_generation_retries() {
+    use rand::Rng;
+
+    let mut rng = CountingRng {
+        rng: ChaChaRng::seed_from_u64(0),
+        draws: 0,
+    };
+
+    // A key generation which rejects its first sample succeeds on the second attempt, with a fresh sample.
+    let mut samples = vec![];
+    let sample = contribution_core::retry_with_rng(&mut rng, contribution_core::KEY_GENERATION_ATTEMPTS, |rng| {
+        samples.push(rng.gen::<u64>());
+        match samples.len() {
+            1 => Err(Phase1Error::KeyGeneration),
+            _ => Ok(samples[samples.len() - 1]),
+        }
+    })
+    .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_ne!(samples[0], samples[1]);
+    assert_eq!(sample, samples[1]);
+    assert_eq!(rng.draws, 2);
+
+    // The error is only returned once every attempt failed.
+    let mut attempts = 0;
+    let result: Result<(), _> = contribution_core::retry_with_rng(&mut rng, 3, |_| {
+        attempts += 1;
+        Err(Phase1Error::KeyGeneration)
+    });
+    assert!(matches!(result, Err(Phase1Error::KeyGeneration)));
+    assert_eq!(attempts, 3);
+
+    // A keypair which generates on the first attempt is the same as without retries.
+    let hash = blank_hash();
+    let (public_key, _) =
+        contribution_core::generate_keypair_with_attempts::<Bls12_377>(&mut ChaChaRng::seed_from_u64(1), &hash, 3)
+            .unwrap();
+    let (expected, _) =
+        contribution_core::generate_keypair::<Bls12_377>(&mut ChaChaRng::seed_from_u64(1), &hash).unwrap();
+    assert_eq!(public_key, expected);
+}
+
+#[cfg(feature = "cbor")]
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_cbor() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = Phase1WASM::contribute_beacon("bls12_377", "groth16", 2, 2, challenge, &[1u8; 32], 4).unwrap();
+
+    let cbor = result.to_cbor().unwrap();
+    let decoded = ContributionResponse::from_cbor(&cbor).unwrap();
+    assert_eq!(decoded.format_version(), RESPONSE_FORMAT_VERSION);
+    assert_eq!(decoded.current_accumulator_hash(), result.current_accumulator_hash());
+    assert_eq!(decoded.response, result.response);
+    assert_eq!(decoded.contribution_hash(), result.contribution_hash());
+    assert_eq!(decoded.public_key(), result.public_key());
+    assert_eq!(decoded.beacon_seed(), result.beacon_seed());
+    assert_eq!(decoded.randomness_commitment(), None);
+    assert_eq!(decoded.to_cbor().unwrap(), cbor);
+
+    // The bytes are byte strings rather than hex, so the CBOR encoding is
+    // around half the length of the JSON one, which is dominated by the response.
+    let json = result.to_json().unwrap();
+    assert!(cbor.len() < json.len() * 6 / 10);
+    assert!(cbor.len() > result.response.len());
+
+    assert!(matches!(
+        ContributionResponse::from_cbor(&cbor[..cbor.len() / 2]),
+        Err(Phase1Error::Encoding(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wasm_initial_challenge() {
+    for (curve_kind, proving_system) in [("bls12_377", "groth16"), ("bls12_377", "marlin"), ("bw6", "groth16")] {
+        let challenge = Phase1WASM::initial_challenge(curve_kind, proving_system, 2, 3).unwrap();
+        let sizes = Phase1WASM::parameter_sizes(curve_kind, proving_system, 2, 3, None, None).unwrap();
+        assert_eq!(challenge.len(), sizes.accumulator_size());
+        assert_eq!(&challenge[..HASH_LENGTH], blank_hash().as_slice());
+        assert_eq!(
+            Phase1WASM::initial_challenge(curve_kind, proving_system, 2, 3).unwrap(),
+            challenge
+        );
+
+        let seed = [7u8; MIN_SEED_LENGTH];
+        let result =
+            Phase1WASM::contribute_full_with_seed(curve_kind, proving_system, 2, 3, challenge.clone(), &seed).unwrap();
+        assert!(
+            Phase1WASM::verify_contribution(curve_kind, proving_system, 2, 3, &challenge, &result.response, None)
+                .unwrap()
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_response_selector() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 3, 2);
+    let challenge = initial_challenge(&parameters).unwrap();
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let selector = Phase1WASM::response_selector("bls12_377", "groth16", 2, 3, None, None, None).unwrap();
+    let counts = Phase1WASM::power_counts("groth16", 3).unwrap();
+    let g1 = selector.select(&result.response, ResponseGroup::G1).unwrap();
+    let g2 = selector.select(&result.response, ResponseGroup::G2).unwrap();
+    assert_eq!(g1.len(), counts.tau_powers_g1() * parameters.curve.g1_compressed_size);
+    assert_eq!(g2.len(), counts.tau_powers_g2() * parameters.curve.g2_compressed_size);
+
+    // The G2 powers follow the G1 powers, which follow the hash.
+    let g2_start = parameters.hash_size + g1.len();
+    assert_eq!(g1, result.response[parameters.hash_size..g2_start]);
+    assert_eq!(g2, result.response[g2_start..g2_start + g2.len()]);
+
+    assert!(matches!(
+        selector.select(&result.response[1..], ResponseGroup::G2),
+        Err(Phase1Error::ResponseLength { .. })
+    ));
+
+    // A chunk of Marlin past the first holds no G2 powers.
+    let selector = Phase1WASM::response_selector("bls12_377", "marlin", 2, 3, Some(1), Some(4), None).unwrap();
+    let parameters = get_parameters_chunked::<Bls12_377>(ProvingSystem::Marlin, 3, 2, 1, 4);
+    let response = vec![0; contribution_core::response_len(&parameters, COMPRESSED_OUTPUT)];
+    assert!(selector.select(&response, ResponseGroup::G2).unwrap().is_empty());
+    assert_eq!(
+        selector.select(&response, ResponseGroup::G1).unwrap().len(),
+        4 * parameters.curve.g1_compressed_size
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_parameters_handle() {
+    let handle = Phase1ParametersHandle::new("bls12_377", "groth16", 2, 3, None, None).unwrap();
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 3, 2);
+    assert_eq!(handle.sizes(), ParameterSizes::from(&parameters));
+
+    // The same handle contributes to each challenge of a transcript in turn.
+    let mut challenge = initial_challenge(&parameters).unwrap();
+    for seed in 0..3 {
+        let result = handle
+            .contribute_with_rng(&challenge, ChaChaRng::seed_from_u64(seed))
+            .unwrap();
+        let expected = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(seed)).unwrap();
+        assert_eq!(result.response, expected.response);
+        assert!(handle.verify(&challenge, &result.response, None).unwrap());
+
+        challenge = next_challenge(&result.response, &parameters).unwrap();
+    }
+
+    assert!(matches!(
+        Phase1ParametersHandle::new("bls12_377", "groth16", 2, 3, Some(2), Some(8)),
+        Err(Phase1Error::ChunkIndexOutOfRange { .. })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_looks_trivial() {
+    use snarkvm_fields::Zero;
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert!(!looks_trivial(&result.response, &parameters, COMPRESSED_OUTPUT));
+    assert!(!Phase1WASM::looks_trivial("bls12_377", "groth16", 2, 2, &result.response).unwrap());
+
+    // The response buffer as it is prepared, without any computation.
+    let length = contribution_core::response_len(&parameters, COMPRESSED_OUTPUT);
+    let hash = calculate_hash(&challenge);
+    for fill in &[
+        ResponseFill::AccumulatorHash,
+        ResponseFill::Zero,
+        ResponseFill::Pattern(0xab),
+    ] {
+        let mut response = vec![];
+        fill_response(&mut response, &hash, length, *fill);
+        assert!(looks_trivial(&response, &parameters, COMPRESSED_OUTPUT));
+    }
+
+    // The second tau_g1 power replaced by the identity.
+    let mut accumulator = Phase1::deserialize(
+        &result.response,
+        COMPRESSED_OUTPUT,
+        CheckForCorrectness::No,
+        &parameters,
+    )
+    .unwrap();
+    accumulator.tau_powers_g1[1] = <Bls12_377 as PairingEngine>::G1Affine::zero();
+    let mut response = result.response.clone();
+    accumulator
+        .serialize(&mut response, COMPRESSED_OUTPUT, &parameters)
+        .unwrap();
+    assert!(looks_trivial(&response, &parameters, COMPRESSED_OUTPUT));
+
+    assert!(!looks_trivial(&result.response[1..], &parameters, COMPRESSED_OUTPUT));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_hash_bytes() {
+    // The Blake2b-512 digest of the empty input.
+    assert_eq!(
+        hex::encode(hash_bytes(b"")),
+        "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+         d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+    );
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let hash = hash_bytes(&result.response);
+    assert_eq!(hash.len(), HASH_LENGTH);
+    assert_eq!(hash, result.contribution_hash());
+    assert_eq!(hash_bytes(&challenge), Phase1WASM::challenge_hash(&challenge));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_wrong_compression() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (compressed, _) = generate_input(&parameters, UseCompression::Yes);
+    let (uncompressed, _) = generate_input(&parameters, COMPRESSED_INPUT);
+
+    // A compressed challenge passed where an uncompressed one is expected.
+    let error = contribute_challenge(&compressed, &parameters, ChaChaRng::seed_from_u64(0)).unwrap_err();
+    match error {
+        Phase1Error::WrongCompression {
+            expected,
+            actual,
+            compressed: true,
+        } => assert_eq!((expected, actual), (uncompressed.len(), compressed.len())),
+        _ => panic!("expected a challenge compression error"),
+    }
+    assert_eq!(error.code(), 34);
+    assert!(error.to_string().contains("the size of a compressed challenge"));
+    let value = Phase1ErrorValue::from(error);
+    assert_eq!(
+        (value.expected(), value.actual()),
+        (Some(uncompressed.len()), Some(compressed.len()))
+    );
+
+    // A challenge of neither length is only reported as the wrong length.
+    assert!(matches!(
+        contribute_challenge(&compressed[1..], &parameters, ChaChaRng::seed_from_u64(0)),
+        Err(Phase1Error::ChallengeLength { .. })
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_phase1_native_verify_batch() {
+    use crate::native::verify_batch;
+
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let first = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    let next = next_challenge(&first.response, &parameters).unwrap();
+    let second = contribute_challenge(&next, &parameters, ChaChaRng::seed_from_u64(1)).unwrap();
+
+    let mut corrupted = first.response.clone();
+    corrupted[parameters.hash_size + parameters.curve.g1_compressed_size + 5] ^= 0xff;
+    let pairs = vec![
+        (challenge.clone(), first.response.clone()),
+        (challenge.clone(), corrupted),
+        (next.clone(), second.response.clone()),
+        // The response to another challenge.
+        (challenge.clone(), second.response.clone()),
+        (next, second.response[1..].to_vec()),
+    ];
+
+    let verdicts = verify_batch(&parameters, pairs);
+    assert_eq!(verdicts.len(), 5);
+    assert!(verdicts[0].as_ref().unwrap());
+    assert!(!verdicts[1].as_ref().unwrap());
+    assert!(verdicts[2].as_ref().unwrap());
+    assert!(!verdicts[3].as_ref().unwrap());
+    assert!(matches!(verdicts[4], Err(Phase1Error::ResponseLength { .. })));
+
+    assert!(verify_batch(&parameters, vec![]).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_empty_challenge() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let error = contribute_challenge(&[], &parameters, ChaChaRng::seed_from_u64(0)).unwrap_err();
+    assert!(matches!(error, Phase1Error::EmptyChallenge));
+    assert_eq!(error.code(), 35);
+
+    assert!(matches!(
+        Phase1WASM::contribute_full_with_seed("bls12_377", "groth16", 2, 2, vec![], &[0u8; 32]),
+        Err(Phase1Error::EmptyChallenge)
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_uncompressed_public_key() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let compressed = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    let options = ContributionOptions {
+        public_key_compression: UseCompression::No,
+        ..Default::default()
+    };
+    let result =
+        contribute_challenge_with_options(&challenge, &parameters, ChaChaRng::seed_from_u64(0), options).unwrap();
+
+    // The body is still compressed, followed by the uncompressed public key.
+    let public_key_size = 3 * parameters.curve.g2_size + 6 * parameters.curve.g1_size;
+    assert_eq!(
+        contribution_core::public_key_len(&parameters, UseCompression::No),
+        public_key_size
+    );
+    assert_eq!(
+        result.response.len(),
+        parameters.contribution_size - parameters.public_key_size + public_key_size
+    );
+    assert_eq!(
+        result.response.len(),
+        contribution_core::response_len_with_public_key(&parameters, COMPRESSED_OUTPUT, UseCompression::No)
+    );
+    assert_eq!(result.response_public_key(), result.public_key());
+    assert_eq!(
+        result.response_without_public_key(),
+        compressed.response_without_public_key()
+    );
+
+    // Both serializations hold the same key.
+    let uncompressed_key = contribution_core::read_public_key::<Bls12_377>(
+        &result.response,
+        COMPRESSED_OUTPUT,
+        UseCompression::No,
+        &parameters,
+    )
+    .unwrap();
+    let compressed_key = contribution_core::read_public_key::<Bls12_377>(
+        &compressed.response,
+        COMPRESSED_OUTPUT,
+        UseCompression::Yes,
+        &parameters,
+    )
+    .unwrap();
+    assert!(uncompressed_key == compressed_key);
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_rate_limiter() {
+    use std::{cell::Cell, rc::Rc};
+
+    let now = Rc::new(Cell::new(1000.0));
+    let clock = now.clone();
+    let mut limiter = RateLimiter::with_clock(3, 600.0, move || clock.get()).unwrap();
+
+    // A burst beyond the limit is rejected, for that participant only.
+    for _ in 0..3 {
+        assert!(limiter.try_acquire("alice").unwrap());
+    }
+    assert!(!limiter.try_acquire("alice").unwrap());
+    assert!(limiter.try_acquire("bob").unwrap());
+
+    // The bucket refills evenly over the window.
+    now.set(1200.0);
+    assert!(limiter.try_acquire("alice").unwrap());
+    assert!(!limiter.try_acquire("alice").unwrap());
+    assert_eq!(limiter.tracked_participants(), 2);
+
+    // It's full again once the window has passed, but never holds more than the limit.
+    now.set(5000.0);
+    for _ in 0..3 {
+        assert!(limiter.try_acquire("alice").unwrap());
+    }
+    assert!(!limiter.try_acquire("alice").unwrap());
+    // The bucket of bob, full again a window later, was dropped.
+    assert_eq!(limiter.tracked_participants(), 1);
+
+    // A clock going backwards refills nothing.
+    now.set(0.0);
+    assert!(!limiter.try_acquire("alice").unwrap());
+
+    // A clock which can't tell the time fails rather than reading as 0.
+    let mut limiter =
+        RateLimiter::with_fallible_clock(3, 600.0, || Err(Phase1Error::Clock("threw".to_string()))).unwrap();
+    assert!(matches!(limiter.try_acquire("alice"), Err(Phase1Error::Clock(_))));
+
+    assert!(matches!(
+        RateLimiter::new(0, 600.0),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+    assert!(matches!(
+        RateLimiter::new(3, 0.0),
+        Err(Phase1Error::InvalidParameters(_))
+    ));
+    assert!(RateLimiter::new(3, 600.0).unwrap().try_acquire("alice").unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_phase1_contribution_response_eq() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let result = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+
+    // The same randomness contributes the same response.
+    let again = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(0)).unwrap();
+    assert_eq!(result, again);
+    assert_eq!(result.clone(), result);
+    assert_eq!(
+        ContributionResponse::from_json(&result.to_json().unwrap()).unwrap(),
+        result
+    );
+    assert!(format!("{:?}", result).contains("ContributionResponse"));
+
+    let other = contribute_challenge(&challenge, &parameters, ChaChaRng::seed_from_u64(1)).unwrap();
+    assert_ne!(result, other);
+
+    let nan = ContributionTiming {
+        hash_ms: f64::NAN,
+        ..Default::default()
+    };
+    assert_eq!(nan, nan);
+    assert_ne!(nan, ContributionTiming::default());
+}
+
+/// A response serving `body` with `status`, and with `content_length` as its
+/// `Content-Length` if there is one, standing in for a coordinator.
+#[cfg(target_arch = "wasm32")]
+fn mock_response(body: &[u8], status: u16, content_length: Option<usize>) -> web_sys::Response {
+    let headers = web_sys::Headers::new().unwrap();
+    if let Some(content_length) = content_length {
+        headers.set("Content-Length", &content_length.to_string()).unwrap();
+    }
+    let mut init = web_sys::ResponseInit::new();
+    init.status(status).headers(&headers);
+    web_sys::Response::new_with_opt_u8_array_and_init(Some(&mut body.to_vec()), &init).unwrap()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+async fn test_phase1_contribute_from_url() {
+    let parameters = get_parameters_full::<Bls12_377>(ProvingSystem::Groth16, 2, 2);
+    let (challenge, _) = generate_input(&parameters, COMPRESSED_INPUT);
+    let length = challenge.len();
+
+    // A data URL serves the challenge without a server.
+    let encoded: String = challenge.iter().map(|byte| format!("%{:02x}", byte)).collect();
+    let url = format!("data:application/octet-stream,{}", encoded);
+    let result = Phase1WASM::contribute_from_url(
+        "bls12_377".to_string(),
+        "groth16".to_string(),
+        2,
+        2,
+        url,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(verify_response(&challenge, &result.response, &parameters).unwrap());
+
+    let read = read_challenge(mock_response(&challenge, 200, Some(length)), length).await;
+    assert_eq!(read.unwrap(), challenge);
+
+    assert!(matches!(
+        read_challenge(mock_response(&challenge, 404, Some(length)), length).await,
+        Err(Phase1Error::FetchStatus { status: 404 })
+    ));
+    // The advertised length is checked before the body is read.
+    assert!(matches!(
+        read_challenge(mock_response(&challenge, 200, Some(length + 1)), length).await,
+        Err(Phase1Error::ChallengeLength { actual, .. }) if actual == length + 1
+    ));
+    // A body shorter than advertised is a cut short download.
+    assert!(matches!(
+        read_challenge(mock_response(&challenge[1..], 200, Some(length)), length).await,
+        Err(Phase1Error::DownloadLength { expected, actual }) if (expected, actual) == (length, length - 1)
+    ));
+    // Without a Content-Length, the body itself must be the length of a challenge.
+    assert!(matches!(
+        read_challenge(mock_response(&challenge[1..], 200, None), length).await,
+        Err(Phase1Error::ChallengeLength { .. })
+    ));
+    assert!(matches!(
+        read_challenge(mock_response(&[challenge.as_slice(), &[0]].concat(), 200, None), length).await,
+        Err(Phase1Error::ChallengeLength { .. })
+    ));
+}
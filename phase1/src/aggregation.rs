@@ -40,7 +40,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
 
             match parameters.proving_system {
                 ProvingSystem::Groth16 => {
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _enter = span.enter();
 
                         t.spawn(|_| {
@@ -57,7 +57,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                         });
 
                         if start < chunk_parameters.powers_length {
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 let _enter = span.enter();
 
                                 t.spawn(|_| {
@@ -114,7 +114,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                 }
 
                 ProvingSystem::Marlin => {
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _enter = span.enter();
 
                         t.spawn(|_| {
@@ -132,7 +132,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
 
                         // handle tau G2
                         if start < 2 + chunk_parameters.total_size_in_log2 {
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 let _enter = span.enter();
                                 t.spawn(|_| {
                                     let _enter = span.enter();
@@ -150,7 +150,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                         }
                         // handle alpha tau G1
                         if start < 3 + 3 * chunk_parameters.total_size_in_log2 {
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 let _enter = span.enter();
 
                                 t.spawn(|_| {
@@ -6,11 +6,8 @@ use setup_utils::{BatchDeserializer, BatchSerializer, Deserializer, Serializer,
 
 use snarkvm_curves::{AffineCurve, PairingEngine};
 
-#[cfg(not(feature = "wasm"))]
 use crate::ContributionMode;
-#[cfg(not(feature = "wasm"))]
 use snarkvm_fields::{FieldParameters, PrimeField, Zero};
-#[cfg(not(feature = "wasm"))]
 use snarkvm_utilities::BitIteratorBE;
 
 #[allow(type_alias_bounds)]
@@ -32,8 +29,10 @@ type AccumulatorElementsRef<'a, E: PairingEngine> = (
     &'a E::G2Affine,
 );
 
+// Verification and decompression run in WASM builds too, so these are compiled
+// into every build.
 cfg_if! {
-    if #[cfg(not(feature = "wasm"))] {
+    if #[cfg(all())] {
         use crate::PublicKey;
 
         /// Given a public key and the accumulator's digest, it hashes each G1 element
@@ -181,7 +180,7 @@ cfg_if! {
                     }
 
                     // Load `batch_size` chunks on each iteration and decompress them
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         t.spawn(|_| {
                             decompress_buffer::<E::G1Affine>(
                                 tau_g1,
@@ -192,7 +191,7 @@ cfg_if! {
                             .expect("could not decompress the tau_g1 elements")
                         });
                         if parameters.other_chunk_size > 0 {
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 t.spawn(|_| {
                                     decompress_buffer::<E::G2Affine>(
                                         tau_g2,
@@ -242,7 +241,7 @@ cfg_if! {
                         decompress_buffer::<E::G2Affine>(tau_g2, in_tau_g2, check_input_for_correctness, (0, parameters.total_size_in_log2 + 2))?;
                     }
 
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                          t.spawn(|_| {
                             decompress_buffer::<E::G1Affine>(
                                 tau_g1,
@@ -3,13 +3,11 @@ pub mod helpers;
 pub mod objects;
 pub use objects::*;
 
-#[cfg(not(feature = "wasm"))]
 mod aggregation;
 mod computation;
 mod initialization;
 mod key_generation;
 mod serialization;
-#[cfg(not(feature = "wasm"))]
 mod verification;
 
 use crate::helpers::{
@@ -18,10 +16,8 @@ use crate::helpers::{
 };
 use setup_utils::*;
 
-#[cfg(not(feature = "wasm"))]
 use crate::helpers::accumulator::*;
 
-#[cfg(not(feature = "wasm"))]
 use snarkvm_fields::Zero;
 
 use snarkvm_curves::{AffineCurve, PairingEngine, ProjectiveCurve};
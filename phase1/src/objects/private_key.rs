@@ -1,9 +1,25 @@
 use snarkvm_curves::PairingEngine;
+use snarkvm_utilities::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 
 /// Contains the secrets τ, α and β that the participant of the ceremony must destroy.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct PrivateKey<E: PairingEngine> {
     pub tau: E::Fr,
     pub alpha: E::Fr,
     pub beta: E::Fr,
 }
+
+impl<E: PairingEngine> PrivateKey<E> {
+    /// Serializes the secrets, for example to resume an interrupted contribution.
+    /// The bytes must be protected as carefully as the key itself.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerializationError> {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserializes the secrets written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Self::deserialize(&mut &bytes[..])
+    }
+}
@@ -68,4 +68,16 @@ impl<E: PairingEngine> PublicKey<E> {
         // The public key is written after the provided position
         Ok(PublicKey::deserialize(&mut &input_map[position..])?)
     }
+
+    /// Serializes the key on its own, as it is written to the end of a response.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerializationError> {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserializes a key written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Self::deserialize(&mut &bytes[..])
+    }
 }
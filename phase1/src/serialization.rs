@@ -39,7 +39,6 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
         })
     }
 
-    #[cfg(not(feature = "wasm"))]
     pub fn decompress(
         input: &[u8],
         output: &mut [u8],
@@ -194,7 +194,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
 
             match parameters.proving_system {
                 ProvingSystem::Groth16 => {
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _enter = span.enter();
 
                         // Process tau_g1 elements.
@@ -235,7 +235,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                                 ContributionMode::Full => (start, end),
                             };
 
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 let _enter = span.enter();
 
                                 // Process tau_g2 elements.
@@ -290,7 +290,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                     });
                 }
                 ProvingSystem::Marlin => {
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _ = span.enter();
 
                         // Process tau_g1 elements.
@@ -409,7 +409,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                     let span = info_span!("batch", start, end);
                     let _enter = span.enter();
 
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _enter = span.enter();
 
                         t.spawn(|_| {
@@ -438,7 +438,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                                 end
                             };
 
-                            rayon::scope(|t| {
+                            rayon_cfg::scope(|t| {
                                 let _enter = span.enter();
 
                                 t.spawn(|_| {
@@ -504,7 +504,7 @@ impl<'a, E: PairingEngine + Sync> Phase1<'a, E> {
                     let span = info_span!("batch", start, end);
                     let _enter = span.enter();
 
-                    rayon::scope(|t| {
+                    rayon_cfg::scope(|t| {
                         let _enter = span.enter();
 
                         t.spawn(|_| {